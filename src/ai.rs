@@ -0,0 +1,316 @@
+// src/ai.rs - コンピュータプレイヤーの探索
+use crate::core::{Board, BoardSize, GameMode, MoveError, Player};
+use crate::game::GameSession;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    // 開始局面の盤面のハッシュ（Board::zobrist_hash）ごとの定跡手
+    static OPENING_BOOK: RefCell<HashMap<u64, (usize, usize)>> = RefCell::new(HashMap::new());
+    // 探索で評価した局面の数（計測用）
+    static NODE_COUNT: Cell<usize> = const { Cell::new(0) };
+    // 取得できる得点の高い順に手を並べてから探索するか
+    static MOVE_ORDERING: Cell<bool> = const { Cell::new(true) };
+}
+
+// 以下の3つは探索の計測・テスト用で、公開APIとしては扱わない
+
+// 手の並べ替えを有効・無効にする（既定では有効。設定はスレッドごと）
+// 無効にすると get_valid_moves の順に探索する。選ばれる手は変わらない
+#[doc(hidden)]
+pub fn set_move_ordering(enabled: bool) {
+    MOVE_ORDERING.with(|ordering| ordering.set(enabled));
+}
+
+// reset_node_count 以降に探索した局面の数
+#[doc(hidden)]
+pub fn node_count() -> usize {
+    NODE_COUNT.with(Cell::get)
+}
+
+#[doc(hidden)]
+pub fn reset_node_count() {
+    NODE_COUNT.with(|count| count.set(0));
+}
+
+// 探索する順に並べた手番のプレイヤーの手（元の順序での番号付き）
+// 即座に得られる得点の高い順で、同点は get_valid_moves の順
+fn ranked_moves(session: &GameSession) -> Vec<(usize, (usize, usize))> {
+    let mover = session.current_player;
+    let mut moves: Vec<(usize, (usize, usize))> =
+        session.valid_moves(mover).into_iter().enumerate().collect();
+    if MOVE_ORDERING.with(Cell::get) {
+        moves.sort_by_key(|&(_, target)| {
+            std::cmp::Reverse(session.score_delta(mover, target).unwrap_or(0))
+        });
+    }
+    moves
+}
+
+// 定跡を設定（既定では空。設定はスレッドごと）
+// ラウンドの最初の手では、盤面が定跡にあれば探索せずにその手を返す
+pub fn set_opening_book(book: HashMap<u64, (usize, usize)>) {
+    OPENING_BOOK.with(|opening_book| *opening_book.borrow_mut() = book);
+}
+
+// ラウンドの最初の手で、定跡にある合法手
+fn book_move(session: &GameSession, player: Player) -> Option<(usize, usize)> {
+    if session.current_player != player || !session.history.is_empty() {
+        return None;
+    }
+
+    let target =
+        OPENING_BOOK.with(|book| book.borrow().get(&session.board.zobrist_hash()).copied())?;
+    session
+        .valid_moves(player)
+        .contains(&target)
+        .then_some(target)
+}
+
+// 深さを指定して最善手を探索（アルファベータ法）
+// 探索は現在の手番から始めるので、player の手番でなければNone
+// 評価値が同じ手は get_valid_moves の順で先のものを選ぶ
+pub fn best_move(session: &GameSession, player: Player, depth: usize) -> Option<(usize, usize)> {
+    book_move(session, player).or_else(|| search_root(session, player, depth, None).flatten())
+}
+
+// 持ち時間内で反復深化しながら最善手を探索
+// 深さ1から順に探索し、時間内に完了した最も深い探索の結果を返す
+pub fn best_move_timed(
+    session: &GameSession,
+    player: Player,
+    budget: Duration,
+) -> Option<(usize, usize)> {
+    if session.current_player != player {
+        return None;
+    }
+    if let Some(target) = book_move(session, player) {
+        return Some(target);
+    }
+
+    // 持ち時間が極端に短くても合法手は返す
+    let mut best = session.valid_moves(player).first().copied();
+    let deadline = Instant::now() + budget;
+    let max_depth = session.board.piece_counts().values().sum::<usize>();
+
+    for depth in 1..=max_depth {
+        match search_root(session, player, depth, Some(deadline)) {
+            Some(result) => best = result,
+            None => break, // 時間切れ
+        }
+    }
+
+    best
+}
+
+// winrate の自己対戦で使う探索の深さ
+pub const WINRATE_DEPTH: usize = 2;
+
+// 自己対戦で各プレイヤーが勝ったラウンドの割合（先手の有利さの計測用）
+// i 局目の盤面は seed + i のシードで生成し、全員が WINRATE_DEPTH の best_move で指す
+// 引き分けはどのプレイヤーにも数えないので、合計は1以下になる
+pub fn winrate(size: BoardSize, mode: GameMode, games: usize, seed: u64) -> HashMap<Player, f32> {
+    let mut wins: HashMap<Player, usize> = HashMap::new();
+
+    for game in 0..games {
+        let board = Board::new_seeded(size, seed.wrapping_add(game as u64));
+        let mut session = GameSession::new_with_board(board, mode);
+        for player in &session.players {
+            wins.entry(*player).or_insert(0);
+        }
+
+        while !session.is_round_over() {
+            let player = session.current_player;
+            let Some(target) = best_move(&session, player, WINRATE_DEPTH) else {
+                break;
+            };
+            if session.process_move(target).is_err() {
+                break;
+            }
+        }
+
+        if let Some(winner) = session.get_round_winner() {
+            *wins.get_mut(&winner).unwrap() += 1;
+        }
+    }
+
+    wins.into_iter()
+        .map(|(player, count)| (player, count as f32 / games.max(1) as f32))
+        .collect()
+}
+
+// ラウンドの終わりまで読み切り、現在の手番のプレイヤーが確保できる得点差とその手を返す
+// 得点差は evaluate と同じ（他のプレイヤーは全員が得点差を縮めるように指すと仮定する）
+// 4x4程度の盤面向け。移動先がなければ手はNone
+pub fn solve(session: &GameSession) -> (i32, Option<(usize, usize)>) {
+    let player = session.current_player;
+    let mut cache = HashMap::new();
+    let mut best: Option<((usize, usize), i32)> = None;
+
+    for target in session.valid_moves(player) {
+        let mut next = session.clone();
+        let Ok(gain) = solve_move(&mut next, player, target) else {
+            continue;
+        };
+
+        let value = gain + solve_value(&next, player, &mut cache);
+        if best.is_none_or(|(_, best_value)| value > best_value) {
+            best = Some((target, value));
+        }
+    }
+
+    let current = evaluate(session, player);
+    match best {
+        Some((target, value)) => (current + value, Some(target)),
+        None => (current, None),
+    }
+}
+
+// 終局までに player が上乗せできる得点差（置換表は盤面のハッシュと手番で引く）
+fn solve_value(
+    session: &GameSession,
+    player: Player,
+    cache: &mut HashMap<(u64, Player), i32>,
+) -> i32 {
+    if session.is_round_over() {
+        return 0;
+    }
+
+    let key = (session.board.zobrist_hash(), session.current_player);
+    if let Some(value) = cache.get(&key) {
+        return *value;
+    }
+
+    let mover = session.current_player;
+    let values = session.valid_moves(mover).into_iter().filter_map(|target| {
+        let mut next = session.clone();
+        let gain = solve_move(&mut next, player, target).ok()?;
+        Some(gain + solve_value(&next, player, cache))
+    });
+    let value = if mover == player {
+        values.max()
+    } else {
+        values.min()
+    }
+    .unwrap_or(0);
+
+    cache.insert(key, value);
+    value
+}
+
+// 手を適用し、player から見た得点差の変化を返す
+fn solve_move(
+    session: &mut GameSession,
+    player: Player,
+    target: (usize, usize),
+) -> Result<i32, MoveError> {
+    let mover = session.current_player;
+    let gain = session.score_delta(mover, target)?;
+    session.process_move(target)?;
+    Ok(if mover == player { gain } else { -gain })
+}
+
+// ルートの探索（時間切れならNone）
+fn search_root(
+    session: &GameSession,
+    player: Player,
+    depth: usize,
+    deadline: Option<Instant>,
+) -> Option<Option<(usize, usize)>> {
+    if session.current_player != player {
+        return Some(None);
+    }
+
+    // (元の順序での番号, 手, 評価値)
+    let mut best: Option<(usize, (usize, usize), i32)> = None;
+
+    for (index, target) in ranked_moves(session) {
+        let mut next = session.clone();
+        if next.process_move(target).is_err() {
+            continue;
+        }
+
+        // 同じ評価値なら元の順序で先の手を選ぶため、それより前の手は同点も正確に評価する
+        let alpha = match best {
+            None => i32::MIN,
+            Some((best_index, _, best_value)) if index < best_index => best_value.saturating_sub(1),
+            Some((_, _, best_value)) => best_value,
+        };
+        let value = alpha_beta(
+            &next,
+            player,
+            depth.saturating_sub(1),
+            alpha,
+            i32::MAX,
+            deadline,
+        )?;
+        let better = best.is_none_or(|(best_index, _, best_value)| {
+            value > best_value || (value == best_value && index < best_index)
+        });
+        if better && (best.is_none() || value > alpha) {
+            best = Some((index, target, value));
+        }
+    }
+
+    Some(best.map(|(_, target, _)| target))
+}
+
+// player から見た評価値（時間切れならNone）
+// player 以外は全員が player の得点を下げるように指すと仮定する
+fn alpha_beta(
+    session: &GameSession,
+    player: Player,
+    depth: usize,
+    mut alpha: i32,
+    mut beta: i32,
+    deadline: Option<Instant>,
+) -> Option<i32> {
+    if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+        return None;
+    }
+    NODE_COUNT.with(|count| count.set(count.get() + 1));
+    if depth == 0 || session.is_round_over() {
+        return Some(evaluate(session, player));
+    }
+
+    let maximizing = session.current_player == player;
+    let mut best = if maximizing { i32::MIN } else { i32::MAX };
+
+    for (_, target) in ranked_moves(session) {
+        let mut next = session.clone();
+        if next.process_move(target).is_err() {
+            continue;
+        }
+
+        let value = alpha_beta(&next, player, depth - 1, alpha, beta, deadline)?;
+        if maximizing {
+            best = best.max(value);
+            alpha = alpha.max(value);
+        } else {
+            best = best.min(value);
+            beta = beta.min(value);
+        }
+        if alpha >= beta {
+            break;
+        }
+    }
+
+    Some(best)
+}
+
+// 自分の得点から他のプレイヤーの得点の合計を引いた値
+fn evaluate(session: &GameSession, player: Player) -> i32 {
+    session
+        .players
+        .iter()
+        .map(|p| {
+            let total = session.scores[p].total;
+            if *p == player {
+                total
+            } else {
+                -total
+            }
+        })
+        .sum()
+}
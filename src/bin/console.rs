@@ -3,11 +3,34 @@ use micattix::ui::ConsoleUI;
 use std::io::{self, Write};
 
 fn main() {
-    println!("Welcome to Micattix!");
+    // --events-json: ゲームイベントをJSON行として標準出力に書き出す
+    let events_json = std::env::args().any(|arg| arg == "--events-json");
+    // --sort-scores: スコアを得点の高い順に表示する
+    let sort_scores = std::env::args().any(|arg| arg == "--sort-scores");
+    // --seed <u64>: 最初の盤面を固定のシードで生成する
+    let seed = parse_seed();
+
+    // --replay <path>: JSON形式のリプレイを1手ずつ表示して終了する
+    if let Some(path) = option_value("--replay") {
+        view_replay_file(&path);
+        return;
+    }
+
+    // --events-json では標準出力をJSON行専用にし、対話用の表示は標準エラーに出す
+    let mut out: Box<dyn Write> = if events_json {
+        Box::new(io::stderr())
+    } else {
+        Box::new(io::stdout())
+    };
+
+    writeln!(out, "Welcome to Micattix!").unwrap();
+    if let Some(seed) = seed {
+        writeln!(out, "Seed: {}", seed).unwrap();
+    }
 
     // ボードサイズを選択
-    print!("Select board size (1: 4x4, 2: 6x6): ");
-    io::stdout().flush().unwrap();
+    write!(out, "Select board size (1: 4x4, 2: 6x6): ").unwrap();
+    out.flush().unwrap();
 
     let mut input = String::new();
     io::stdin().read_line(&mut input).unwrap();
@@ -16,14 +39,14 @@ fn main() {
         "1" => BoardSize::Small,
         "2" => BoardSize::Large,
         _ => {
-            println!("Invalid selection, using 4x4 board");
+            writeln!(out, "Invalid selection, using 4x4 board").unwrap();
             BoardSize::Small
         }
     };
 
     // プレイヤー数を選択
-    print!("Select game mode (1: 2 Players, 2: 4 Players): ");
-    io::stdout().flush().unwrap();
+    write!(out, "Select game mode (1: 2 Players, 2: 4 Players): ").unwrap();
+    out.flush().unwrap();
 
     let mut input = String::new();
     io::stdin().read_line(&mut input).unwrap();
@@ -32,12 +55,63 @@ fn main() {
         "1" => GameMode::TwoPlayers,
         "2" => GameMode::FourPlayers,
         _ => {
-            println!("Invalid selection, using 2 Players mode");
+            writeln!(out, "Invalid selection, using 2 Players mode").unwrap();
             GameMode::TwoPlayers
         }
     };
 
     // UIを初期化して実行
-    let mut ui = ConsoleUI::new(size, game_mode);
+    let mut ui = match seed {
+        Some(seed) => ConsoleUI::new_seeded(size, game_mode, seed),
+        None => ConsoleUI::new(size, game_mode),
+    };
+    ui.set_sort_scores(sort_scores);
+    if events_json {
+        #[cfg(feature = "serde")]
+        ui.set_events_json();
+        #[cfg(not(feature = "serde"))]
+        writeln!(out, "--events-json requires the `serde` feature; ignoring").unwrap();
+    }
     ui.run();
 }
+
+// オプションの直後の引数
+fn option_value(name: &str) -> Option<String> {
+    let args: Vec<String> = std::env::args().collect();
+    let position = args.iter().position(|arg| arg == name)?;
+    args.get(position + 1).cloned()
+}
+
+// リプレイのファイルを読み込んで表示
+#[cfg(feature = "serde")]
+fn view_replay_file(path: &str) {
+    let replay: micattix::replay::Replay = match std::fs::read_to_string(path)
+        .map_err(|e| e.to_string())
+        .and_then(|json| serde_json::from_str(&json).map_err(|e| e.to_string()))
+    {
+        Ok(replay) => replay,
+        Err(e) => {
+            println!("Failed to load replay {}: {}", path, e);
+            return;
+        }
+    };
+    micattix::ui::view_replay(&replay);
+}
+
+#[cfg(not(feature = "serde"))]
+fn view_replay_file(_path: &str) {
+    println!("--replay requires the `serde` feature");
+}
+
+// --seed の値を取得（不正な値は警告して無視する）
+fn parse_seed() -> Option<u64> {
+    let args: Vec<String> = std::env::args().collect();
+    let position = args.iter().position(|arg| arg == "--seed")?;
+    match args.get(position + 1).map(|value| value.parse::<u64>()) {
+        Some(Ok(seed)) => Some(seed),
+        _ => {
+            eprintln!("--seed requires an unsigned integer; using a random board");
+            None
+        }
+    }
+}
@@ -5,13 +5,148 @@ use ggez::input::keyboard::KeyInput;
 use ggez::input::mouse::MouseButton;
 use ggez::mint::Point2;
 use ggez::{Context, GameResult};
-use micattix::core::{BoardSize, GameMode, Piece, Player};
+use micattix::core::{BoardSize, GameMode, MoveError, Piece, Player};
 use micattix::game::{GameEvent, GameEventListener, GameManager};
 use std::io::{self, Write};
 
 const CELL_SIZE: f32 = 80.0;
 const MARGIN: f32 = 50.0;
 
+// 盤面の描画サイズ（高DPI環境では拡大する）
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Geometry {
+    cell_size: f32,
+    margin: f32,
+}
+
+impl Default for Geometry {
+    fn default() -> Self {
+        Self {
+            cell_size: CELL_SIZE,
+            margin: MARGIN,
+        }
+    }
+}
+
+impl Geometry {
+    // 既定のサイズを拡大・縮小したもの
+    fn scaled(scale: f32) -> Self {
+        Self {
+            cell_size: CELL_SIZE * scale,
+            margin: MARGIN * scale,
+        }
+    }
+
+    // 既定のサイズに対する倍率（文字の大きさなどに使う）
+    fn scale(&self) -> f32 {
+        self.cell_size / CELL_SIZE
+    }
+
+    // セルの左上の座標
+    fn cell_origin(&self, row: usize, col: usize) -> (f32, f32) {
+        (
+            self.margin + col as f32 * self.cell_size,
+            self.margin + row as f32 * self.cell_size,
+        )
+    }
+
+    // クリック位置からセルを求める（盤面の外ならNone）
+    fn cell_at(&self, x: f32, y: f32, rows: usize, cols: usize) -> Option<(usize, usize)> {
+        pixel_to_cell(x, y, self.margin, self.cell_size, rows, cols)
+    }
+
+    // 盤面より下の情報パネルの行の位置（margin単位）
+    fn panel_y(&self, rows: usize, margins: f32) -> f32 {
+        self.margin * margins + rows as f32 * self.cell_size
+    }
+
+    // ウィンドウの大きさ
+    fn window_size(&self, rows: usize, cols: usize) -> (f32, f32) {
+        (
+            self.margin * 2.0 + cols as f32 * self.cell_size,
+            self.panel_y(rows, 6.0),
+        )
+    }
+}
+
+// ピクセル座標からセルを求める（盤面の外ならNone）
+// 盤面の左上は (margin, margin)。セルの右端・下端の線は隣のセルに含まれる
+fn pixel_to_cell(
+    x: f32,
+    y: f32,
+    margin: f32,
+    cell: f32,
+    rows: usize,
+    cols: usize,
+) -> Option<(usize, usize)> {
+    let board_width = cols as f32 * cell;
+    let board_height = rows as f32 * cell;
+    if x < margin || y < margin || x >= margin + board_width || y >= margin + board_height {
+        return None;
+    }
+
+    // 浮動小数点の誤差で端を越えた場合も盤面内に収める
+    let col = (((x - margin) / cell) as usize).min(cols - 1);
+    let row = (((y - margin) / cell) as usize).min(rows - 1);
+    Some((row, col))
+}
+
+// 描画に使う色のセット
+#[derive(Debug, Clone, Copy)]
+struct Theme {
+    window: Color,         // ウィンドウ全体の背景
+    background: Color,     // 盤面の背景
+    grid: Color,           // セルの枠線
+    text: Color,           // 情報パネルの文字
+    positive_text: Color,  // 正の数の駒
+    negative_text: Color,  // 負の数の駒
+    cross: Color,          // クロスチップ
+    axis: Color,           // 移動できる軸
+    valid_move: Color,     // 有効な移動先
+    selection: Color,      // 選択中のセル
+    positive_badge: Color, // 取得値バッジ（正）
+    message: Color,        // メッセージ
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            window: Color::WHITE,
+            background: Color::from_rgb(230, 230, 230),
+            grid: Color::BLACK,
+            text: Color::BLACK,
+            positive_text: Color::BLACK,
+            negative_text: Color::RED,
+            cross: Color::RED,
+            axis: Color::from_rgba(0, 120, 255, 40),
+            valid_move: Color::from_rgba(0, 255, 0, 100),
+            selection: Color::from_rgba(255, 255, 0, 100),
+            positive_badge: Color::from_rgb(40, 140, 40),
+            message: Color::RED,
+        }
+    }
+}
+
+impl Theme {
+    // 暗い背景のテーマ
+    fn dark() -> Self {
+        Self {
+            window: Color::from_rgb(24, 24, 28),
+            background: Color::from_rgb(48, 48, 56),
+            grid: Color::from_rgb(140, 140, 150),
+            text: Color::from_rgb(230, 230, 230),
+            positive_text: Color::from_rgb(235, 235, 235),
+            negative_text: Color::from_rgb(255, 110, 110),
+            cross: Color::from_rgb(255, 200, 80),
+            axis: Color::from_rgba(80, 160, 255, 50),
+            valid_move: Color::from_rgba(80, 220, 120, 90),
+            selection: Color::from_rgba(255, 220, 60, 90),
+            positive_badge: Color::from_rgb(50, 160, 90),
+            message: Color::from_rgb(255, 140, 140),
+        }
+    }
+}
+
 struct MicattixGame {
     manager: GameManager,
     selected_cell: Option<(usize, usize)>,
@@ -19,10 +154,13 @@ struct MicattixGame {
     message_timer: f32,
     round_ending: bool,
     round_end_timer: f32,
+    show_values: bool, // 有効な移動先に取得値のバッジを表示するか
+    theme: Theme,
+    geometry: Geometry,
 }
 
 impl MicattixGame {
-    pub fn new(_ctx: &mut Context, size: BoardSize) -> Self {
+    pub fn new(_ctx: &mut Context, size: BoardSize, theme: Theme) -> Self {
         // デフォルトで2プレイヤーモードを使用
         let manager = GameManager::new(size, GameMode::TwoPlayers);
 
@@ -33,23 +171,33 @@ impl MicattixGame {
             message_timer: 0.0,
             round_ending: false,
             round_end_timer: 0.0,
+            show_values: false,
+            theme,
+            geometry: Geometry::default(),
         }
     }
 
     fn draw_board(&self, canvas: &mut Canvas, ctx: &mut Context) -> GameResult {
         let (rows, cols) = self.manager.session.board.size.dimensions();
+        let cell_size = self.geometry.cell_size;
+        let scale = self.geometry.scale();
 
         // 背景を描画
-        let board_width = cols as f32 * CELL_SIZE;
-        let board_height = rows as f32 * CELL_SIZE;
-
-        let board_rect = graphics::Rect::new(MARGIN, MARGIN, board_width, board_height);
+        let board_width = cols as f32 * cell_size;
+        let board_height = rows as f32 * cell_size;
+
+        let board_rect = graphics::Rect::new(
+            self.geometry.margin,
+            self.geometry.margin,
+            board_width,
+            board_height,
+        );
 
         let board_mesh = graphics::Mesh::new_rectangle(
             ctx,
             graphics::DrawMode::fill(),
             board_rect,
-            Color::from_rgb(230, 230, 230),
+            self.theme.background,
         )?;
 
         canvas.draw(&board_mesh, DrawParam::default());
@@ -58,16 +206,15 @@ impl MicattixGame {
         for row in 0..rows {
             for col in 0..cols {
                 // セルの位置を計算
-                let x = MARGIN + col as f32 * CELL_SIZE;
-                let y = MARGIN + row as f32 * CELL_SIZE;
+                let (x, y) = self.geometry.cell_origin(row, col);
 
                 // セルの枠を描画
-                let cell_rect = graphics::Rect::new(x, y, CELL_SIZE, CELL_SIZE);
+                let cell_rect = graphics::Rect::new(x, y, cell_size, cell_size);
                 let cell_mesh = graphics::Mesh::new_rectangle(
                     ctx,
-                    graphics::DrawMode::stroke(2.0),
+                    graphics::DrawMode::stroke(2.0 * scale),
                     cell_rect,
-                    Color::BLACK,
+                    self.theme.grid,
                 )?;
 
                 canvas.draw(&cell_mesh, DrawParam::default());
@@ -76,60 +223,135 @@ impl MicattixGame {
                 let piece = self.manager.session.board.get_piece(row, col);
                 match piece {
                     Piece::Number(n) => {
-                        let text = Text::new(TextFragment::new(n.to_string()).scale(32.0));
+                        let text = Text::new(TextFragment::new(n.to_string()).scale(32.0 * scale));
                         let text_pos = Point2 {
-                            x: x + CELL_SIZE / 2.0 - 10.0,
-                            y: y + CELL_SIZE / 2.0 - 16.0,
+                            x: x + cell_size / 2.0 - 10.0 * scale,
+                            y: y + cell_size / 2.0 - 16.0 * scale,
                         };
 
-                        let color = if n < 0 { Color::RED } else { Color::BLACK };
+                        let color = if n < 0 {
+                            self.theme.negative_text
+                        } else {
+                            self.theme.positive_text
+                        };
 
                         canvas.draw(&text, DrawParam::default().dest(text_pos).color(color));
                     }
                     Piece::Cross => {
-                        let text = Text::new(TextFragment::new("X").scale(32.0));
+                        let text = Text::new(TextFragment::new("X").scale(32.0 * scale));
+                        let text_pos = Point2 {
+                            x: x + cell_size / 2.0 - 10.0 * scale,
+                            y: y + cell_size / 2.0 - 16.0 * scale,
+                        };
+
+                        canvas.draw(
+                            &text,
+                            DrawParam::default().dest(text_pos).color(self.theme.cross),
+                        );
+                    }
+                    Piece::Bonus(_) => {
+                        let text = Text::new(TextFragment::new("+").scale(32.0 * scale));
                         let text_pos = Point2 {
-                            x: x + CELL_SIZE / 2.0 - 10.0,
-                            y: y + CELL_SIZE / 2.0 - 16.0,
+                            x: x + cell_size / 2.0 - 10.0 * scale,
+                            y: y + cell_size / 2.0 - 16.0 * scale,
                         };
 
-                        canvas.draw(&text, DrawParam::default().dest(text_pos).color(Color::RED));
+                        canvas.draw(
+                            &text,
+                            DrawParam::default()
+                                .dest(text_pos)
+                                .color(self.theme.positive_badge),
+                        );
                     }
                     Piece::Empty => {}
                 }
             }
         }
 
-        // 有効な移動先をハイライト
+        // 現在のプレイヤーが移動できる軸をハイライト
         let current_player = self.manager.session.current_player;
-        let valid_moves = self.manager.session.board.get_valid_moves(current_player);
+        let direction = self.manager.session.direction_of(current_player);
+        for (row, col, _) in self
+            .manager
+            .session
+            .board
+            .line_cells_for_direction(direction)
+        {
+            let (x, y) = self.geometry.cell_origin(row, col);
+
+            let axis_rect = graphics::Rect::new(x, y, cell_size, cell_size);
+            let axis_mesh = graphics::Mesh::new_rectangle(
+                ctx,
+                graphics::DrawMode::fill(),
+                axis_rect,
+                self.theme.axis,
+            )?;
+
+            canvas.draw(&axis_mesh, DrawParam::default());
+        }
+
+        // 有効な移動先をハイライト
+        let valid_moves = self.manager.session.valid_moves(current_player);
 
         for (row, col) in valid_moves {
-            let x = MARGIN + col as f32 * CELL_SIZE;
-            let y = MARGIN + row as f32 * CELL_SIZE;
+            let (x, y) = self.geometry.cell_origin(row, col);
 
-            let highlight_rect = graphics::Rect::new(x, y, CELL_SIZE, CELL_SIZE);
+            let highlight_rect = graphics::Rect::new(x, y, cell_size, cell_size);
             let highlight_mesh = graphics::Mesh::new_rectangle(
                 ctx,
                 graphics::DrawMode::fill(),
                 highlight_rect,
-                Color::from_rgba(0, 255, 0, 100),
+                self.theme.valid_move,
             )?;
 
             canvas.draw(&highlight_mesh, DrawParam::default());
+
+            // 取得値のバッジを右上に描画（負の値は赤）
+            if self.show_values {
+                if let Some(value) = self.manager.session.board.get_piece(row, col).value() {
+                    let badge_color = if value < 0 {
+                        self.theme.negative_text
+                    } else {
+                        self.theme.positive_badge
+                    };
+                    let badge_rect = graphics::Rect::new(
+                        x + cell_size - 34.0 * scale,
+                        y + 4.0 * scale,
+                        30.0 * scale,
+                        20.0 * scale,
+                    );
+                    let badge_mesh = graphics::Mesh::new_rectangle(
+                        ctx,
+                        graphics::DrawMode::fill(),
+                        badge_rect,
+                        badge_color,
+                    )?;
+                    canvas.draw(&badge_mesh, DrawParam::default());
+
+                    let badge_text =
+                        Text::new(TextFragment::new(format!("{:+}", value)).scale(16.0 * scale));
+                    let badge_pos = Point2 {
+                        x: x + cell_size - 31.0 * scale,
+                        y: y + 6.0 * scale,
+                    };
+                    canvas.draw(
+                        &badge_text,
+                        DrawParam::default().dest(badge_pos).color(Color::WHITE),
+                    );
+                }
+            }
         }
 
         // 選択されたセルをハイライト
         if let Some((row, col)) = self.selected_cell {
-            let x = MARGIN + col as f32 * CELL_SIZE;
-            let y = MARGIN + row as f32 * CELL_SIZE;
+            let (x, y) = self.geometry.cell_origin(row, col);
 
-            let select_rect = graphics::Rect::new(x, y, CELL_SIZE, CELL_SIZE);
+            let select_rect = graphics::Rect::new(x, y, cell_size, cell_size);
             let select_mesh = graphics::Mesh::new_rectangle(
                 ctx,
                 graphics::DrawMode::fill(),
                 select_rect,
-                Color::from_rgba(255, 255, 0, 100),
+                self.theme.selection,
             )?;
 
             canvas.draw(&select_mesh, DrawParam::default());
@@ -139,82 +361,89 @@ impl MicattixGame {
     }
 
     fn draw_info(&self, canvas: &mut Canvas, _ctx: &mut Context) -> GameResult {
+        let rows = self.manager.session.board.size.dimensions().0;
+        let margin = self.geometry.margin;
+        let scale = self.geometry.scale();
+
         // 現在のプレイヤー情報
         let current_player = self.manager.session.current_player;
         let player_text = match current_player {
-            Player::First => {
-                Text::new(TextFragment::new("Current Player: First (Horizontal)").scale(24.0))
-            }
-            Player::Second => {
-                Text::new(TextFragment::new("Current Player: Second (Vertical)").scale(24.0))
-            }
-            Player::Third => {
-                Text::new(TextFragment::new("Current Player: Third (Horizontal)").scale(24.0))
-            }
-            Player::Fourth => {
-                Text::new(TextFragment::new("Current Player: Fourth (Vertical)").scale(24.0))
-            }
+            Player::First => Text::new(
+                TextFragment::new("Current Player: First (Horizontal)").scale(24.0 * scale),
+            ),
+            Player::Second => Text::new(
+                TextFragment::new("Current Player: Second (Vertical)").scale(24.0 * scale),
+            ),
+            Player::Third => Text::new(
+                TextFragment::new("Current Player: Third (Horizontal)").scale(24.0 * scale),
+            ),
+            Player::Fourth => Text::new(
+                TextFragment::new("Current Player: Fourth (Vertical)").scale(24.0 * scale),
+            ),
+        };
+        let player_pos = Point2 {
+            x: margin,
+            y: 20.0 * scale,
         };
-        let player_pos = Point2 { x: MARGIN, y: 20.0 };
-
-        canvas.draw(&player_text, DrawParam::default().dest(player_pos));
 
-        // スコア情報
-        let first_score = &self.manager.session.scores[&Player::First];
-        let second_score = &self.manager.session.scores[&Player::Second];
+        canvas.draw(
+            &player_text,
+            DrawParam::default().dest(player_pos).color(self.theme.text),
+        );
 
-        // ゲームモードに応じたスコア表示
-        let score_text = if self.manager.session.game_mode == GameMode::TwoPlayers {
-            Text::new(
-                TextFragment::new(format!(
-                    "Scores - First: {} | Second: {}",
-                    first_score.total, second_score.total
-                ))
-                .scale(20.0),
-            )
-        } else {
-            // 4人プレイモードの場合
-            let third_score = &self.manager.session.scores[&Player::Third];
-            let fourth_score = &self.manager.session.scores[&Player::Fourth];
-            Text::new(
-                TextFragment::new(format!(
-                    "Scores - First: {} | Second: {} | Third: {} | Fourth: {}",
-                    first_score.total, second_score.total, third_score.total, fourth_score.total
-                ))
-                .scale(20.0),
-            )
-        };
+        // スコア情報（現在のトップには * を付ける）
+        let session = &self.manager.session;
+        let leader = session.current_leader();
+        let scores: Vec<String> = session
+            .players
+            .iter()
+            .map(|player| {
+                let mark = if leader == Some(*player) { "*" } else { "" };
+                format!("{:?}: {}{}", player, session.scores[player].total, mark)
+            })
+            .collect();
+        let score_text = Text::new(
+            TextFragment::new(format!("Scores - {}", scores.join(" | "))).scale(20.0 * scale),
+        );
         let score_pos = Point2 {
-            x: MARGIN,
-            y: MARGIN * 2.0 + self.manager.session.board.size.dimensions().0 as f32 * CELL_SIZE,
+            x: margin,
+            y: self.geometry.panel_y(rows, 2.0),
         };
 
-        canvas.draw(&score_text, DrawParam::default().dest(score_pos));
+        canvas.draw(
+            &score_text,
+            DrawParam::default().dest(score_pos).color(self.theme.text),
+        );
 
         // メッセージ
         if self.message_timer > 0.0 {
-            let message_text = Text::new(TextFragment::new(&self.message).scale(24.0));
+            let message_text = Text::new(TextFragment::new(&self.message).scale(24.0 * scale));
             let message_pos = Point2 {
-                x: MARGIN,
-                y: MARGIN * 2.5 + self.manager.session.board.size.dimensions().0 as f32 * CELL_SIZE,
+                x: margin,
+                y: self.geometry.panel_y(rows, 2.5),
             };
 
             canvas.draw(
                 &message_text,
-                DrawParam::default().dest(message_pos).color(Color::RED),
+                DrawParam::default()
+                    .dest(message_pos)
+                    .color(self.theme.message),
             );
         }
 
         // ラウンド情報
         let round_text = Text::new(
-            TextFragment::new(format!("Round: {}", self.manager.session.round)).scale(24.0),
+            TextFragment::new(format!("Round: {}", self.manager.session.round)).scale(24.0 * scale),
         );
         let round_pos = Point2 {
-            x: MARGIN + 400.0,
-            y: 20.0,
+            x: margin + 400.0 * scale,
+            y: 20.0 * scale,
         };
 
-        canvas.draw(&round_text, DrawParam::default().dest(round_pos));
+        canvas.draw(
+            &round_text,
+            DrawParam::default().dest(round_pos).color(self.theme.text),
+        );
 
         // 合計スコア情報
         let total_first = self.manager.session.total_scores[&Player::First];
@@ -227,7 +456,7 @@ impl MicattixGame {
                     "Total Scores - First: {} | Second: {}",
                     total_first, total_second
                 ))
-                .scale(20.0),
+                .scale(20.0 * scale),
             )
         } else {
             // 4人プレイモードの場合
@@ -238,75 +467,173 @@ impl MicattixGame {
                     "Total Scores - First: {} | Second: {} | Third: {} | Fourth: {}",
                     total_first, total_second, total_third, total_fourth
                 ))
-                .scale(20.0),
+                .scale(20.0 * scale),
             )
         };
         let total_pos = Point2 {
-            x: MARGIN,
-            y: MARGIN * 3.0 + self.manager.session.board.size.dimensions().0 as f32 * CELL_SIZE,
+            x: margin,
+            y: self.geometry.panel_y(rows, 3.0),
+        };
+
+        canvas.draw(
+            &total_text,
+            DrawParam::default().dest(total_pos).color(self.theme.text),
+        );
+
+        // 各プレイヤーのラウンド勝利数
+        let rounds_won: Vec<String> = session
+            .players
+            .iter()
+            .map(|player| {
+                let wins = session
+                    .round_winners_history()
+                    .iter()
+                    .filter(|winner| **winner == Some(*player))
+                    .count();
+                format!("{:?}: {}", player, wins)
+            })
+            .collect();
+        let rounds_text = Text::new(
+            TextFragment::new(format!("Rounds won — {}", rounds_won.join(", ")))
+                .scale(18.0 * scale),
+        );
+        let rounds_pos = Point2 {
+            x: margin,
+            y: self.geometry.panel_y(rows, 4.0),
         };
 
-        canvas.draw(&total_text, DrawParam::default().dest(total_pos));
+        canvas.draw(
+            &rounds_text,
+            DrawParam::default().dest(rounds_pos).color(self.theme.text),
+        );
 
         // ゲーム説明
         let help_text = Text::new(
-            TextFragment::new("Click on highlighted cells to move. ESC to quit. N for new round.")
-                .scale(18.0),
+            TextFragment::new("Click on highlighted cells to move. ESC to quit. N for new round. V to show values.")
+                .scale(18.0 * scale),
         );
         let help_pos = Point2 {
-            x: MARGIN,
-            y: MARGIN * 3.5 + self.manager.session.board.size.dimensions().0 as f32 * CELL_SIZE,
+            x: margin,
+            y: self.geometry.panel_y(rows, 3.5),
         };
 
-        canvas.draw(&help_text, DrawParam::default().dest(help_pos));
+        canvas.draw(
+            &help_text,
+            DrawParam::default().dest(help_pos).color(self.theme.text),
+        );
 
         Ok(())
     }
 
-    fn handle_click(&mut self, x: f32, y: f32) {
-        // ラウンド終了処理中は操作を受け付けない
-        if self.round_ending {
-            return;
-        }
-
-        // クリック位置がボード上かチェック
-        if x < MARGIN || y < MARGIN {
-            return;
-        }
-
+    // 各プレイヤーのラウンド得点を横棒で表示（負の得点は軸より左に赤で描く）
+    fn draw_score_bars(&self, canvas: &mut Canvas, ctx: &mut Context) -> GameResult {
         let (rows, cols) = self.manager.session.board.size.dimensions();
-        let board_width = cols as f32 * CELL_SIZE;
-        let board_height = rows as f32 * CELL_SIZE;
+        let margin = self.geometry.margin;
+        let scale = self.geometry.scale();
+        let session = &self.manager.session;
+
+        let label_width = 80.0 * scale;
+        let bar_height = 12.0 * scale;
+        let spacing = 18.0 * scale;
+        let half_width = (cols as f32 * self.geometry.cell_size - label_width) / 2.0;
+        let axis_x = margin + label_width + half_width;
+
+        // 最も得点の大きいプレイヤーの棒が半分の幅になるようにする（最低10点分）
+        let extent = session
+            .players
+            .iter()
+            .map(|player| session.scores[player].total.abs())
+            .max()
+            .unwrap_or(0)
+            .max(10) as f32;
+
+        for (i, player) in session.players.iter().enumerate() {
+            let y = self.geometry.panel_y(rows, 4.5) + i as f32 * spacing;
+
+            let label = Text::new(TextFragment::new(format!("{:?}", player)).scale(14.0 * scale));
+            canvas.draw(
+                &label,
+                DrawParam::default()
+                    .dest(Point2 { x: margin, y })
+                    .color(self.theme.text),
+            );
 
-        if x > MARGIN + board_width || y > MARGIN + board_height {
-            return;
+            let total = session.scores[player].total;
+            if total != 0 {
+                let width = half_width * (total as f32 / extent).clamp(-1.0, 1.0);
+                let (x, color) = if total > 0 {
+                    (axis_x, self.theme.positive_text)
+                } else {
+                    (axis_x + width, self.theme.negative_text)
+                };
+                let bar_rect = graphics::Rect::new(x, y, width.abs(), bar_height);
+                let bar_mesh = graphics::Mesh::new_rectangle(
+                    ctx,
+                    graphics::DrawMode::fill(),
+                    bar_rect,
+                    color,
+                )?;
+                canvas.draw(&bar_mesh, DrawParam::default());
+            }
         }
 
-        // セル位置を計算
-        let col = ((x - MARGIN) / CELL_SIZE) as usize;
-        let row = ((y - MARGIN) / CELL_SIZE) as usize;
+        // 0点の軸
+        let axis_rect = graphics::Rect::new(
+            axis_x,
+            self.geometry.panel_y(rows, 4.5),
+            1.0,
+            session.players.len() as f32 * spacing,
+        );
+        let axis_mesh = graphics::Mesh::new_rectangle(
+            ctx,
+            graphics::DrawMode::fill(),
+            axis_rect,
+            self.theme.grid,
+        )?;
+        canvas.draw(&axis_mesh, DrawParam::default());
+
+        Ok(())
+    }
 
-        if row >= rows || col >= cols {
+    fn handle_click(&mut self, x: f32, y: f32) {
+        // ラウンド終了処理中は操作を受け付けない
+        if self.round_ending {
             return;
         }
 
+        // クリック位置からセルを求める（盤面の外なら無視）
+        let (rows, cols) = self.manager.session.board.size.dimensions();
+        let (row, col) = match self.geometry.cell_at(x, y, rows, cols) {
+            Some(cell) => cell,
+            None => return,
+        };
+
         // 有効な移動先かチェック
         let current_player = self.manager.session.current_player;
-        let valid_moves = self.manager.session.board.get_valid_moves(current_player);
+        let valid_moves = self.manager.session.valid_moves(current_player);
 
         if valid_moves.contains(&(row, col)) {
             // 移動を実行
             self.manager.make_move((row, col));
             self.selected_cell = None;
 
-            // ラウンド終了チェック
-            if self.manager.session.is_round_over() {
+            // ラウンド終了チェック（ゲームが終了した場合は次のラウンドに進まない）
+            if self.manager.session.is_round_over() && !self.manager.session.is_game_over() {
                 self.round_ending = true;
                 self.round_end_timer = 3.0;
             }
         } else {
+            // クロスチップ自身のクリックはエラーを表示せずに無視する
+            let reason = self.manager.session.explain_illegal((row, col));
+            if matches!(reason, Some(MoveError::IsCrossPosition(_))) {
+                return;
+            }
+
             self.selected_cell = Some((row, col));
-            self.message = "Invalid move! Select a highlighted cell.".to_string();
+            self.message = match reason {
+                Some(reason) => format!("Invalid move! {}.", reason),
+                None => "Invalid move! Select a highlighted cell.".to_string(),
+            };
             self.message_timer = 2.0;
         }
     }
@@ -331,7 +658,7 @@ impl GameEventListener for MicattixGame {
                 self.message_timer = 3.0;
             }
             GameEvent::MoveMade(player, target, piece) => {
-                if let Piece::Number(value) = piece {
+                if let Some(value) = piece.value() {
                     self.message = format!(
                         "{:?} moved to {:?} and got {} points",
                         player, target, value
@@ -341,18 +668,32 @@ impl GameEventListener for MicattixGame {
                 }
                 self.message_timer = 2.0;
             }
+            GameEvent::ScoreUpdated(..) => {
+                // スコアは描画時にセッションから読み直すので何もしない
+            }
+            GameEvent::ExtraTurnGranted(player) => {
+                self.message = format!("{:?} gets an extra turn!", player);
+                self.message_timer = 2.0;
+            }
             GameEvent::InvalidMove(_player, _target, reason) => {
                 self.message = format!("Invalid move: {}", reason);
                 self.message_timer = 2.0;
             }
-            GameEvent::RoundEnded(winner, _scores) => {
+            GameEvent::SidesSwapped => {
+                self.message = "Sides swapped!".to_string();
+                self.message_timer = 2.0;
+            }
+            GameEvent::BoardGenerated(_) => {
+                // 盤面は描画時にセッションから読み直すので何もしない
+            }
+            GameEvent::RoundEnded(winner, _scores, _pieces) => {
                 match winner {
                     Some(w) => self.message = format!("Round ended! Winner: {:?}", w),
                     None => self.message = "Round ended in a draw!".to_string(),
                 }
                 self.message_timer = 5.0;
             }
-            GameEvent::GameEnded(winner, _scores) => {
+            GameEvent::GameEnded(winner, _scores, _pieces) => {
                 match winner {
                     Some(w) => self.message = format!("Game ended! Overall winner: {:?}", w),
                     None => self.message = "Game ended in a draw!".to_string(),
@@ -384,10 +725,11 @@ impl EventHandler for MicattixGame {
     }
 
     fn draw(&mut self, ctx: &mut Context) -> GameResult {
-        let mut canvas = Canvas::from_frame(ctx, Color::WHITE);
+        let mut canvas = Canvas::from_frame(ctx, self.theme.window);
 
         self.draw_board(&mut canvas, ctx)?;
         self.draw_info(&mut canvas, ctx)?;
+        self.draw_score_bars(&mut canvas, ctx)?;
 
         canvas.finish(ctx)?;
         Ok(())
@@ -423,6 +765,10 @@ impl EventHandler for MicattixGame {
                     self.message_timer = 2.0;
                 }
             }
+            Some(ggez::input::keyboard::KeyCode::V) => {
+                // 取得値バッジの表示を切り替え
+                self.show_values = !self.show_values;
+            }
             _ => {}
         }
         Ok(())
@@ -430,6 +776,31 @@ impl EventHandler for MicattixGame {
 }
 
 fn main() -> GameResult {
+    // --theme dark: 暗い配色で表示
+    let args: Vec<String> = std::env::args().collect();
+    // --scale 2.0: 盤面の表示倍率（省略時はディスプレイの倍率を使う）
+    let scale = match args.iter().position(|arg| arg == "--scale") {
+        Some(index) => match args.get(index + 1).map(|value| value.parse::<f32>()) {
+            Some(Ok(value)) if value > 0.0 => Some(value),
+            _ => {
+                println!("Invalid scale, using the display scale factor");
+                None
+            }
+        },
+        None => None,
+    };
+    let theme = match args.iter().position(|arg| arg == "--theme") {
+        Some(index) => match args.get(index + 1).map(String::as_str) {
+            Some("dark") => Theme::dark(),
+            Some("light") | None => Theme::default(),
+            Some(other) => {
+                println!("Unknown theme '{}', using default theme", other);
+                Theme::default()
+            }
+        },
+        None => Theme::default(),
+    };
+
     println!("Welcome to Micattix!");
     println!("Select board size:");
     println!("1: 4x4");
@@ -470,10 +841,9 @@ fn main() -> GameResult {
         BoardSize::Large => "Micattix - 6x6",
     };
 
-    // ウィンドウサイズをボードサイズに応じて調整
+    // ウィンドウサイズをボードサイズに応じて調整（倍率は作成後に反映）
     let (rows, cols) = size.dimensions();
-    let window_width = MARGIN * 2.0 + cols as f32 * CELL_SIZE;
-    let window_height = MARGIN * 4.0 + rows as f32 * CELL_SIZE;
+    let (window_width, window_height) = Geometry::default().window_size(rows, cols);
 
     let cb = ggez::ContextBuilder::new("micattix", "micattix-author")
         .window_setup(WindowSetup::default().title(window_title))
@@ -485,10 +855,18 @@ fn main() -> GameResult {
     let (mut ctx, event_loop) = cb.build()?;
 
     // ゲームインスタンスを作成
-    let mut game = MicattixGame::new(&mut ctx, size);
+    let mut game = MicattixGame::new(&mut ctx, size, theme);
 
-    // ゲームモードを設定
-    game.manager.session.game_mode = game_mode;
+    // 表示倍率を反映してウィンドウを作り直す
+    let scale = scale.unwrap_or(ctx.gfx.window().scale_factor() as f32);
+    game.geometry = Geometry::scaled(scale);
+    let (window_width, window_height) = game.geometry.window_size(rows, cols);
+    ctx.gfx.set_drawable_size(window_width, window_height)?;
+
+    // ゲームモードを設定（ゲーム開始前なので失敗しない）
+    game.manager
+        .set_game_mode(game_mode)
+        .expect("game mode must be set before the game starts");
 
     // ゲーム開始
     game.manager.start_game();
@@ -496,3 +874,55 @@ fn main() -> GameResult {
     // イベントループを実行
     event::run(ctx, event_loop, game)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_at_inverts_cell_origin() {
+        for scale in [1.0, 1.5, 2.0] {
+            let geometry = Geometry::scaled(scale);
+            for (row, col) in [(0, 0), (1, 3), (5, 5), (3, 2)] {
+                let (x, y) = geometry.cell_origin(row, col);
+                let center = geometry.cell_size / 2.0;
+                assert_eq!(geometry.cell_at(x + 1.0, y + 1.0, 6, 6), Some((row, col)));
+                assert_eq!(
+                    geometry.cell_at(x + center, y + center, 6, 6),
+                    Some((row, col))
+                );
+            }
+
+            // 盤面の外
+            assert_eq!(geometry.cell_at(geometry.margin - 1.0, 100.0, 6, 6), None);
+            let (x, y) = geometry.cell_origin(6, 0);
+            assert_eq!(geometry.cell_at(x + 1.0, y + 1.0, 6, 6), None);
+        }
+    }
+
+    #[test]
+    fn test_pixel_to_cell_inside() {
+        assert_eq!(pixel_to_cell(60.0, 60.0, 50.0, 80.0, 4, 4), Some((0, 0)));
+        assert_eq!(pixel_to_cell(215.0, 135.0, 50.0, 80.0, 4, 4), Some((1, 2)));
+        assert_eq!(pixel_to_cell(369.0, 369.0, 50.0, 80.0, 4, 4), Some((3, 3)));
+    }
+
+    #[test]
+    fn test_pixel_to_cell_boundary() {
+        // 左上の端は盤面内、セルの境界は右（下）のセル
+        assert_eq!(pixel_to_cell(50.0, 50.0, 50.0, 80.0, 4, 4), Some((0, 0)));
+        assert_eq!(pixel_to_cell(130.0, 50.0, 50.0, 80.0, 4, 4), Some((0, 1)));
+        assert_eq!(pixel_to_cell(50.0, 130.0, 50.0, 80.0, 4, 4), Some((1, 0)));
+        // 右下の端は盤面の外
+        assert_eq!(pixel_to_cell(370.0, 100.0, 50.0, 80.0, 4, 4), None);
+        assert_eq!(pixel_to_cell(100.0, 370.0, 50.0, 80.0, 4, 4), None);
+    }
+
+    #[test]
+    fn test_pixel_to_cell_outside() {
+        assert_eq!(pixel_to_cell(49.9, 100.0, 50.0, 80.0, 4, 4), None);
+        assert_eq!(pixel_to_cell(100.0, -5.0, 50.0, 80.0, 4, 4), None);
+        assert_eq!(pixel_to_cell(1000.0, 1000.0, 50.0, 80.0, 6, 6), None);
+        assert_eq!(pixel_to_cell(100.0, 100.0, 50.0, 80.0, 0, 0), None);
+    }
+}
@@ -1,16 +1,20 @@
 // src/core.rs - コアとなるゲームロジック
 use rand::prelude::*;
+use rand::rngs::StdRng;
+use std::collections::BTreeMap;
 use std::fmt;
 
 // ゲームモード定義
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameMode {
     TwoPlayers,
     FourPlayers,
 }
 
 // ゲーム盤のサイズ定義
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BoardSize {
     Small, // 4x4
     Large, // 6x6
@@ -23,10 +27,25 @@ impl BoardSize {
             BoardSize::Large => (6, 6),
         }
     }
+
+    // 行数・列数から盤面サイズを逆引き
+    pub fn from_dimensions(rows: usize, cols: usize) -> Option<BoardSize> {
+        [BoardSize::Small, BoardSize::Large]
+            .into_iter()
+            .find(|size| size.dimensions() == (rows, cols))
+    }
+
+    // 盤面の中央のマス
+    // 偶数サイズでは中央の2マスのうち小さい方（左上寄り）を選ぶ
+    pub fn center(&self) -> (usize, usize) {
+        let (rows, cols) = self.dimensions();
+        ((rows - 1) / 2, (cols - 1) / 2)
+    }
 }
 
 // プレイヤー定義
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Player {
     First,  // 横軸移動
     Second, // 縦軸移動
@@ -35,12 +54,29 @@ pub enum Player {
 }
 
 // 移動方向
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MoveDirection {
     Horizontal, // 横
     Vertical,   // 縦
 }
 
+// クロスチップの移動ルール
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Movement {
+    #[default]
+    Jump, // 同じ行・列の任意の駒へ移動できる
+    SlideToFirst, // 空きマスは飛び越えるが、左右（上下）それぞれ最初に当たる駒にしか移動できない
+    Magnet,       // 移動先は Jump と同じだが、途中で通過した駒もまとめて取得する
+}
+
+// 盤面の埋まり具合から見たラウンドの局面（Board::game_phase）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamePhase {
+    Opening, // 埋まっているマスの割合が OPENING_FILL_RATIO 以上
+    Mid,
+    End, // 埋まっているマスの割合が END_FILL_RATIO 以下
+}
+
 impl Player {
     pub fn direction(&self) -> MoveDirection {
         match self {
@@ -96,10 +132,39 @@ impl Player {
 
 // 盤面上の駒
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Piece {
-    Number(i32), // 数値の駒
-    Cross,       // クロスチップ
-    Empty,       // 空きマス
+    Number(i32),      // 数値の駒
+    Cross,            // クロスチップ
+    Empty,            // 空きマス
+    Bonus(BonusKind), // 特殊マス（得点にはならない）
+}
+
+// 特殊マスの種類
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum BonusKind {
+    ExtraTurn, // 取ったプレイヤーがもう一度移動する
+}
+
+impl Piece {
+    // 数値の駒なら値を返す
+    pub fn value(&self) -> Option<i32> {
+        match self {
+            Piece::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    // 得点になる駒か
+    pub fn is_scoring(&self) -> bool {
+        self.value().is_some()
+    }
+
+    // 取ることのできる駒か（クロスチップと空きマス以外）
+    pub fn is_takeable(&self) -> bool {
+        !matches!(self, Piece::Cross | Piece::Empty)
+    }
 }
 
 impl fmt::Display for Piece {
@@ -108,41 +173,227 @@ impl fmt::Display for Piece {
             Piece::Number(n) => write!(f, "{:>3}", n),
             Piece::Cross => write!(f, "  X"),
             Piece::Empty => write!(f, "   "),
+            Piece::Bonus(BonusKind::ExtraTurn) => write!(f, "  +"),
+        }
+    }
+}
+
+// 移動に関するエラー
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveError {
+    InvalidTarget((usize, usize)),     // 移動できないマス
+    PlayerNotInGame(Player),           // ゲームに参加していないプレイヤー
+    OutOfBounds((usize, usize)),       // 盤面の外
+    IsCrossPosition((usize, usize)),   // クロスチップ自身のマス
+    EmptyCell((usize, usize)),         // 既に取られた空きマス
+    NegativeForbidden((usize, usize)), // ルールで禁止された負の駒のマス
+    RoundOver,                         // ラウンドはすでに終了している
+    NotOnLine((usize, usize)),         // クロスチップと同じ行（列）にない
+    Blocked((usize, usize)),           // 途中の駒を飛び越える移動（Movement::SlideToFirst）
+    ForcedCapture((usize, usize)),     // より得点の高い駒を取れる（Rules::forced_max_capture）
+}
+
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::InvalidTarget(target) => write!(f, "Invalid move to {:?}", target),
+            MoveError::PlayerNotInGame(player) => write!(f, "{:?} is not in the game", player),
+            MoveError::OutOfBounds(target) => write!(f, "{:?} is outside the board", target),
+            MoveError::IsCrossPosition(_) => write!(f, "That is the cross itself"),
+            MoveError::EmptyCell(target) => write!(f, "The cell at {:?} is already empty", target),
+            MoveError::NegativeForbidden(target) => {
+                write!(
+                    f,
+                    "Capturing the negative piece at {:?} is not allowed",
+                    target
+                )
+            }
+            MoveError::RoundOver => write!(f, "The round is already over"),
+            MoveError::NotOnLine(target) => {
+                write!(f, "{:?} is not in line with the cross", target)
+            }
+            MoveError::Blocked(target) => {
+                write!(f, "Another piece is in the way to {:?}", target)
+            }
+            MoveError::ForcedCapture(target) => {
+                write!(f, "{:?} is not the highest-value capture available", target)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MoveError {}
+
+// 盤面の読み込み・生成に関するエラー
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoardError {
+    InvalidToken(String),          // 解釈できないトークン
+    RaggedRow(usize),              // 他の行と列数が異なる行（0始まり）
+    UnsupportedSize(usize, usize), // 対応していない盤面サイズ
+    CrossCount(usize),             // クロスチップの数が1つではない
+    Unbalanced(usize),             // 指定の試行回数内に偏りの小さい盤面を生成できなかった
+    OutOfBounds((usize, usize)),   // 盤面の外の位置
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardError::InvalidToken(token) => write!(f, "Invalid token '{}'", token),
+            BoardError::RaggedRow(row) => {
+                write!(f, "Row {} has a different number of cells", row)
+            }
+            BoardError::UnsupportedSize(rows, cols) => {
+                write!(f, "Unsupported board size {}x{}", rows, cols)
+            }
+            BoardError::CrossCount(count) => {
+                write!(f, "Expected exactly one cross, found {}", count)
+            }
+            BoardError::Unbalanced(attempts) => {
+                write!(f, "No balanced board found in {} attempts", attempts)
+            }
+            BoardError::OutOfBounds(position) => {
+                write!(f, "{:?} is outside the board", position)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+// Board::to_matrix でクロスと空きマスを表す値
+pub const MATRIX_CROSS: i32 = i32::MIN;
+pub const MATRIX_EMPTY: i32 = 0;
+
+// Board::game_phase で序盤・終盤とみなす Board::fill_ratio の境界
+pub const OPENING_FILL_RATIO: f32 = 0.75;
+pub const END_FILL_RATIO: f32 = 0.25;
+
+// Board::new_balanced が盤面を生成し直す最大回数
+pub const BALANCE_ATTEMPTS: usize = 1000;
+
+// 盤面の生成方法（独自の駒セットを使う場合に実装する）
+pub trait BoardGenerator: fmt::Debug {
+    fn generate(&self, size: BoardSize, rng: &mut dyn RngCore) -> Board;
+}
+
+// 標準の駒セットをランダムに配置する生成方法
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultGenerator;
+
+impl BoardGenerator for DefaultGenerator {
+    fn generate(&self, size: BoardSize, rng: &mut dyn RngCore) -> Board {
+        Board::generate(size, rng)
+    }
+}
+
+// ルールのオプション
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rules {
+    pub allow_negative_capture: bool, // falseなら負の駒のマスには移動できない
+    pub movement: Movement,           // クロスチップの移動ルール
+    pub forced_max_capture: bool,     // trueなら最も得点の高い駒を取る手しか選べない
+    pub last_mover_bonus: i32,        // 最後の駒を取って盤面を空にしたプレイヤーへのボーナス得点
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            allow_negative_capture: true,
+            movement: Movement::Jump,
+            forced_max_capture: false,
+            last_mover_bonus: 0,
         }
     }
 }
 
 // 盤面の状態
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Board {
     pub size: BoardSize,
-    pub pieces: Vec<Vec<Piece>>,
+    pub pieces: Vec<Piece>, // 行優先で1列に並べたマス（添字は row * cols + col）
     pub cross_position: (usize, usize),
+    pub rules: Rules,
 }
 
 impl Board {
-    // 新しい盤面を生成
+    // 新しい盤面を生成（rand フィーチャーが無効な場合は new_seeded を使う）
+    #[cfg(feature = "rand")]
     pub fn new(size: BoardSize) -> Self {
+        DefaultGenerator.generate(size, &mut rand::thread_rng())
+    }
+
+    // シードを指定して盤面を生成（同じシードなら同じ配置になる）
+    pub fn new_seeded(size: BoardSize, seed: u64) -> Self {
+        Board::generate(size, &mut StdRng::seed_from_u64(seed))
+    }
+
+    // 指定した乱数生成器で駒を配置した盤面を生成
+    fn generate(size: BoardSize, rng: &mut (impl Rng + ?Sized)) -> Self {
         let (rows, cols) = size.dimensions();
-        let pieces = vec![vec![Piece::Empty; cols]; rows];
+        let pieces = vec![Piece::Empty; rows * cols];
         let cross_position = (0, 0); // 仮の初期位置
 
         let mut board = Board {
             size,
             pieces,
             cross_position,
+            rules: Rules::default(),
         };
 
-        board.initialize();
+        board.initialize(rng);
         board
     }
 
-    // 盤面を初期化（駒をランダムに配置）
-    fn initialize(&mut self) {
-        let (rows, cols) = self.size.dimensions();
+    // シードを指定して生成し、クロスチップを指定位置に置いた盤面
+    // 元々その位置にあった駒はクロスチップのあった位置に移す（盤面の外ならエラー）
+    pub fn with_cross_at(
+        size: BoardSize,
+        seed: u64,
+        position: (usize, usize),
+    ) -> Result<Self, BoardError> {
+        let (rows, cols) = size.dimensions();
+        if position.0 >= rows || position.1 >= cols {
+            return Err(BoardError::OutOfBounds(position));
+        }
+
+        let mut board = Board::new_seeded(size, seed);
+        let (row, col) = board.cross_position;
+
+        let from = board.index(row, col);
+        let to = board.index(position.0, position.1);
+        board.pieces.swap(from, to);
+        board.cross_position = position;
+        Ok(board)
+    }
+
+    // クロスチップを中央に置いた盤面（パズル用）
+    pub fn new_cross_centered(size: BoardSize, seed: u64) -> Self {
+        // 中央は常に盤面の内側にある
+        Board::with_cross_at(size, seed, size.center()).unwrap()
+    }
+
+    // 日付ごとに決まる「今日の盤面」
+    // シードは日付を YYYYMMDD の数値にしたもの（例: 2024年3月9日 → 20240309）
+    pub fn daily(size: BoardSize, date: (u32, u32, u32)) -> Self {
+        let (year, month, day) = date;
+        let seed = year as u64 * 10_000 + month as u64 * 100 + day as u64;
+        Board::new_seeded(size, seed)
+    }
+
+    // balance_score が max_variance 以下になるまで盤面を生成し直す
+    // 同じシードなら同じ盤面になる。BALANCE_ATTEMPTS 回で見つからなければエラー
+    pub fn new_balanced(size: BoardSize, seed: u64, max_variance: f32) -> Result<Self, BoardError> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..BALANCE_ATTEMPTS)
+            .map(|_| Board::generate(size, &mut rng))
+            .find(|board| board.balance_score() <= max_variance)
+            .ok_or(BoardError::Unbalanced(BALANCE_ATTEMPTS))
+    }
 
+    // 盤面を初期化（駒をランダムに配置）
+    fn initialize(&mut self, rng: &mut (impl Rng + ?Sized)) {
         // 駒のセットを作成
-        let mut pieces_set = match self.size {
+        let pieces_set = match self.size {
             BoardSize::Small => {
                 // 1～7を各2個、8を1個
                 let mut set = Vec::new();
@@ -178,23 +429,29 @@ impl Board {
             }
         };
 
-        // 駒をシャッフル
-        let mut rng = rand::thread_rng();
-        pieces_set.shuffle(&mut rng);
+        self.place_pieces(pieces_set, rng);
+    }
+
+    // 駒のセットをシャッフルして盤面に配置し、クロスチップの位置を記録
+    pub fn place_pieces(&mut self, mut pieces: Vec<Piece>, rng: &mut (impl Rng + ?Sized)) {
+        let (rows, cols) = self.size.dimensions();
 
         // 盤面に駒を配置
         let total_cells = rows * cols;
         assert_eq!(
-            pieces_set.len(),
+            pieces.len(),
             total_cells,
             "駒の数が盤面のセル数と一致しません"
         );
 
+        // 駒をシャッフル
+        pieces.shuffle(rng);
+
         let mut index = 0;
         for row in 0..rows {
             for col in 0..cols {
-                self.pieces[row][col] = pieces_set[index];
-                if pieces_set[index] == Piece::Cross {
+                self.pieces[index] = pieces[index];
+                if pieces[index] == Piece::Cross {
                     self.cross_position = (row, col);
                 }
                 index += 1;
@@ -203,17 +460,62 @@ impl Board {
     }
 
     // 有効な移動先の一覧を取得
+    // 順序は固定: 横方向は列の昇順、縦方向は行の昇順（AIの同点時の選択がこの順序に依存する）
     pub fn get_valid_moves(&self, player: Player) -> Vec<(usize, usize)> {
+        self.get_valid_moves_for_direction(player.direction())
+    }
+
+    // 移動方向を指定して有効な移動先の一覧を取得（順序は get_valid_moves と同じ）
+    // rules.movement の移動ルールに従う
+    pub fn get_valid_moves_for_direction(&self, direction: MoveDirection) -> Vec<(usize, usize)> {
+        self.valid_moves_for(direction, self.rules.movement)
+    }
+
+    // 移動ルールを指定して有効な移動先の一覧を取得（順序は get_valid_moves と同じ）
+    pub fn get_valid_moves_with(&self, player: Player, movement: Movement) -> Vec<(usize, usize)> {
+        self.valid_moves_for(player.direction(), movement)
+    }
+
+    fn valid_moves_for(&self, direction: MoveDirection, movement: Movement) -> Vec<(usize, usize)> {
+        let moves = self.reachable_moves(direction, movement);
+        if !self.rules.forced_max_capture {
+            return moves;
+        }
+
+        // 強制取得ルールでは取得できる得点が最大の手だけを残す
+        let best = moves
+            .iter()
+            .map(|&target| self.capture_value(target, movement))
+            .max();
+        moves
+            .into_iter()
+            .filter(|&target| Some(self.capture_value(target, movement)) == best)
+            .collect()
+    }
+
+    // 移動先に取れる駒があるマスの一覧（Rules::forced_max_capture は考慮しない）
+    fn reachable_moves(&self, direction: MoveDirection, movement: Movement) -> Vec<(usize, usize)> {
+        if movement == Movement::SlideToFirst {
+            // クロスチップから両方向へ進み、最初に当たった駒だけを候補にする
+            let (before, after) = self.neighbors_for_direction(direction);
+            return before
+                .into_iter()
+                .chain(after)
+                .filter(|&(_, _, piece)| self.is_capturable(piece))
+                .map(|(r, c, _)| (r, c))
+                .collect();
+        }
+
         let (row, col) = self.cross_position;
         let (rows, cols) = self.size.dimensions();
 
         let mut valid_moves = Vec::new();
 
-        match player.direction() {
+        match direction {
             MoveDirection::Horizontal => {
                 // 横方向の移動
                 for c in 0..cols {
-                    if c != col && self.pieces[row][c] != Piece::Empty {
+                    if c != col && self.is_capturable(self.pieces[self.index(row, c)]) {
                         valid_moves.push((row, c));
                     }
                 }
@@ -221,7 +523,7 @@ impl Board {
             MoveDirection::Vertical => {
                 // 縦方向の移動
                 for r in 0..rows {
-                    if r != row && self.pieces[r][col] != Piece::Empty {
+                    if r != row && self.is_capturable(self.pieces[self.index(r, col)]) {
                         valid_moves.push((r, col));
                     }
                 }
@@ -231,59 +533,577 @@ impl Board {
         valid_moves
     }
 
+    // プレイヤーの軸上でクロスチップから左右（上下）それぞれ最も近い駒
+    // 空きマスは飛ばし、その方向に駒がなければNone（左・上が先）
+    #[allow(clippy::type_complexity)]
+    pub fn axis_neighbors(
+        &self,
+        player: Player,
+    ) -> (Option<(usize, usize, Piece)>, Option<(usize, usize, Piece)>) {
+        self.neighbors_for_direction(player.direction())
+    }
+
+    #[allow(clippy::type_complexity)]
+    fn neighbors_for_direction(
+        &self,
+        direction: MoveDirection,
+    ) -> (Option<(usize, usize, Piece)>, Option<(usize, usize, Piece)>) {
+        let line: Vec<_> = self.line_cells_for_direction(direction).collect();
+        let (row, col) = self.cross_position;
+        let cross = match direction {
+            MoveDirection::Horizontal => col,
+            MoveDirection::Vertical => row,
+        };
+
+        let before = line[..cross]
+            .iter()
+            .rev()
+            .find(|cell| cell.2 != Piece::Empty);
+        let after = line[cross + 1..].iter().find(|cell| cell.2 != Piece::Empty);
+        (before.copied(), after.copied())
+    }
+
+    // ルール上取得できる駒か（空きマスは取得できない）
+    fn is_capturable(&self, piece: Piece) -> bool {
+        match piece {
+            Piece::Empty => false,
+            Piece::Number(n) if n < 0 => self.rules.allow_negative_capture,
+            _ => true,
+        }
+    }
+
+    // クロスチップのある行（横）または列（縦）の全マスを列挙（クロスチップ自身も含む）
+    pub fn line_cells(&self, player: Player) -> impl Iterator<Item = (usize, usize, Piece)> + '_ {
+        self.line_cells_for_direction(player.direction())
+    }
+
+    // 移動方向を指定してクロスチップのある行または列の全マスを列挙
+    pub fn line_cells_for_direction(
+        &self,
+        direction: MoveDirection,
+    ) -> impl Iterator<Item = (usize, usize, Piece)> + '_ {
+        let (row, col) = self.cross_position;
+        let (rows, cols) = self.size.dimensions();
+
+        let cells: Box<dyn Iterator<Item = (usize, usize)>> = match direction {
+            MoveDirection::Horizontal => Box::new((0..cols).map(move |c| (row, c))),
+            MoveDirection::Vertical => Box::new((0..rows).map(move |r| (r, col))),
+        };
+        cells.map(move |(r, c)| (r, c, self.pieces[self.index(r, c)]))
+    }
+
+    // 指定したマスが有効な移動先か判定（get_valid_movesを生成せずに判定）
+    pub fn is_valid_target(&self, player: Player, target: (usize, usize)) -> bool {
+        self.is_valid_target_for_direction(player.direction(), target)
+    }
+
+    // 移動方向を指定して有効な移動先か判定
+    pub fn is_valid_target_for_direction(
+        &self,
+        direction: MoveDirection,
+        target: (usize, usize),
+    ) -> bool {
+        self.check_target_for_direction(direction, target).is_ok()
+    }
+
+    // 指定の方向で移動できないマスについて、その理由を返す
+    pub fn check_target_for_direction(
+        &self,
+        direction: MoveDirection,
+        target: (usize, usize),
+    ) -> Result<(), MoveError> {
+        let (rows, cols) = self.size.dimensions();
+        let (row, col) = self.cross_position;
+
+        if target.0 >= rows || target.1 >= cols {
+            return Err(MoveError::OutOfBounds(target));
+        }
+        if target == self.cross_position {
+            return Err(MoveError::IsCrossPosition(target));
+        }
+
+        let aligned = match direction {
+            MoveDirection::Horizontal => target.0 == row,
+            MoveDirection::Vertical => target.1 == col,
+        };
+        if !aligned {
+            return Err(MoveError::NotOnLine(target));
+        }
+        if self.pieces[self.index(target.0, target.1)] == Piece::Empty {
+            return Err(MoveError::EmptyCell(target));
+        }
+        if !self.is_capturable(self.pieces[self.index(target.0, target.1)]) {
+            return Err(MoveError::NegativeForbidden(target));
+        }
+        if self.rules.movement == Movement::SlideToFirst
+            && !self
+                .reachable_moves(direction, Movement::SlideToFirst)
+                .contains(&target)
+        {
+            return Err(MoveError::Blocked(target));
+        }
+        if self.rules.forced_max_capture
+            && !self
+                .valid_moves_for(direction, self.rules.movement)
+                .contains(&target)
+        {
+            return Err(MoveError::ForcedCapture(target));
+        }
+
+        Ok(())
+    }
+
+    // クロスチップから target までの行（列）に沿った距離
+    // クロスチップと同じ行・列にない場合や盤面の外の場合はNone（クロスチップ自身は0）
+    pub fn distance_to(&self, target: (usize, usize)) -> Option<usize> {
+        let (rows, cols) = self.size.dimensions();
+        let (row, col) = self.cross_position;
+
+        if target.0 >= rows || target.1 >= cols {
+            None
+        } else if target.0 == row {
+            Some(target.1.abs_diff(col))
+        } else if target.1 == col {
+            Some(target.0.abs_diff(row))
+        } else {
+            None
+        }
+    }
+
+    // Movement::Magnet で target へ移動したときに通過して取得する駒（クロスチップに近い順）
+    // 他の移動ルールや、target がクロスチップと同じ行・列にない場合は空
+    pub fn passed_pieces(&self, target: (usize, usize)) -> Vec<((usize, usize), Piece)> {
+        if self.rules.movement != Movement::Magnet {
+            return Vec::new();
+        }
+        self.pieces_between(target)
+    }
+
+    // クロスチップと target の間にある取れる駒（クロスチップに近い順）
+    fn pieces_between(&self, target: (usize, usize)) -> Vec<((usize, usize), Piece)> {
+        let (row, col) = self.cross_position;
+        let between: Vec<(usize, usize)> = if target.0 == row && target.1 != col {
+            if target.1 > col {
+                (col + 1..target.1).map(|c| (row, c)).collect()
+            } else {
+                (target.1 + 1..col).rev().map(|c| (row, c)).collect()
+            }
+        } else if target.1 == col && target.0 != row {
+            if target.0 > row {
+                (row + 1..target.0).map(|r| (r, col)).collect()
+            } else {
+                (target.0 + 1..row).rev().map(|r| (r, col)).collect()
+            }
+        } else {
+            Vec::new()
+        };
+
+        between
+            .into_iter()
+            .map(|(r, c)| ((r, c), self.get_piece(r, c)))
+            .filter(|&(_, piece)| self.is_capturable(piece))
+            .collect()
+    }
+
+    // 移動ルールに従って target へ移動したときに得られる得点（Movement::Magnet では通過した駒も含む）
+    fn capture_value(&self, target: (usize, usize), movement: Movement) -> i32 {
+        let own = self.get_piece(target.0, target.1).value().unwrap_or(0);
+        if movement != Movement::Magnet {
+            return own;
+        }
+        own + self
+            .pieces_between(target)
+            .iter()
+            .filter_map(|(_, piece)| piece.value())
+            .sum::<i32>()
+    }
+
+    // 移動した場合に取得できる駒を確認（盤面は変更しない）
+    pub fn peek_capture(&self, player: Player, target: (usize, usize)) -> Option<Piece> {
+        if self.is_valid_target(player, target) {
+            Some(self.pieces[self.index(target.0, target.1)])
+        } else {
+            None
+        }
+    }
+
     // 駒を移動して取得
-    pub fn make_move(&mut self, player: Player, target: (usize, usize)) -> Result<Piece, String> {
-        let valid_moves = self.get_valid_moves(player);
+    pub fn make_move(
+        &mut self,
+        player: Player,
+        target: (usize, usize),
+    ) -> Result<Piece, MoveError> {
+        self.make_move_for_direction(player.direction(), target)
+    }
 
-        if !valid_moves.contains(&target) {
-            return Err(format!("Invalid move to {:?}", target));
+    // 移動方向を指定して駒を移動して取得
+    pub fn make_move_for_direction(
+        &mut self,
+        direction: MoveDirection,
+        target: (usize, usize),
+    ) -> Result<Piece, MoveError> {
+        // クロスチップ自身のマス、負の駒の禁止、途中の駒、強制取得はUIが区別できるようにそのまま返す
+        match self.check_target_for_direction(direction, target) {
+            Ok(()) => {}
+            Err(
+                e @ (MoveError::IsCrossPosition(_)
+                | MoveError::NegativeForbidden(_)
+                | MoveError::Blocked(_)
+                | MoveError::ForcedCapture(_)),
+            ) => return Err(e),
+            Err(_) => return Err(MoveError::InvalidTarget(target)),
         }
 
         // 移動先の駒を記録
-        let piece = self.pieces[target.0][target.1];
+        let piece = self.pieces[self.index(target.0, target.1)];
+
+        // Movement::Magnet では通過した駒も取り除く
+        for ((r, c), _) in self.passed_pieces(target) {
+            let index = self.index(r, c);
+            self.pieces[index] = Piece::Empty;
+        }
 
         // クロスチップを移動
-        self.pieces[self.cross_position.0][self.cross_position.1] = Piece::Empty;
-        self.pieces[target.0][target.1] = Piece::Cross;
+        let from = self.index(self.cross_position.0, self.cross_position.1);
+        let to = self.index(target.0, target.1);
+        self.pieces[from] = Piece::Empty;
+        self.pieces[to] = Piece::Cross;
         self.cross_position = target;
 
         Ok(piece)
     }
 
+    // 取れる駒（数値の駒と特殊マス）が残っているマスのビットマスク（ビット位置は row * cols + col）
+    // 8x8までの盤面を想定
+    pub fn occupancy(&self) -> u64 {
+        let mut mask = 0u64;
+
+        for (index, piece) in self.pieces.iter().enumerate() {
+            if piece.is_takeable() {
+                mask |= 1 << index;
+            }
+        }
+
+        mask
+    }
+
+    // 指定した行に残っている駒の数（クロスチップと空きマスは数えない）
+    pub fn count_nonempty_in_row(&self, row: usize) -> usize {
+        let (_, cols) = self.size.dimensions();
+        (0..cols)
+            .filter(|col| self.get_piece(row, *col).is_takeable())
+            .count()
+    }
+
+    // 指定した列に残っている駒の数（クロスチップと空きマスは数えない）
+    pub fn count_nonempty_in_column(&self, col: usize) -> usize {
+        let (rows, _) = self.size.dimensions();
+        (0..rows)
+            .filter(|row| self.get_piece(*row, col).is_takeable())
+            .count()
+    }
+
+    // 数値の駒の配置だけを比較（クロスチップと空きマスは同じ空白として扱う）
+    pub fn same_numbers(&self, other: &Board) -> bool {
+        self.size == other.size
+            && self
+                .pieces
+                .iter()
+                .zip(&other.pieces)
+                .all(|(a, b)| a.value() == b.value())
+    }
+
+    // 正の駒の値が行・列にどれだけ偏っているか（各行と各列の正の値の合計の分散）
+    // 0に近いほど均等で、どのプレイヤーの軸にも大きな得点が集中していない
+    pub fn balance_score(&self) -> f32 {
+        let (rows, cols) = self.size.dimensions();
+        let positive = |row: usize, col: usize| {
+            self.pieces[self.index(row, col)]
+                .value()
+                .filter(|n| *n > 0)
+                .unwrap_or(0)
+        };
+
+        let sums: Vec<f32> = (0..rows)
+            .map(|r| (0..cols).map(|c| positive(r, c)).sum::<i32>())
+            .chain((0..cols).map(|c| (0..rows).map(|r| positive(r, c)).sum::<i32>()))
+            .map(|sum| sum as f32)
+            .collect();
+
+        let mean = sums.iter().sum::<f32>() / sums.len() as f32;
+        sums.iter().map(|sum| (sum - mean).powi(2)).sum::<f32>() / sums.len() as f32
+    }
+
+    // 盤面のZobristハッシュ（探索の置換表のキーに使う）
+    // 同じ配置なら同じ値になり、駒を1つ動かすと2マス分のキーだけが変わる
+    pub fn zobrist_hash(&self) -> u64 {
+        self.pieces
+            .iter()
+            .enumerate()
+            .fold(0, |hash, (index, piece)| hash ^ zobrist_key(index, *piece))
+    }
+
+    // 指定した駒がある最初のマス（行優先で探す）
+    pub fn find_piece(&self, piece: Piece) -> Option<(usize, usize)> {
+        self.find_all(piece).into_iter().next()
+    }
+
+    // 指定した駒があるマスを全て列挙（行優先の順）
+    pub fn find_all(&self, piece: Piece) -> Vec<(usize, usize)> {
+        let (_, cols) = self.size.dimensions();
+        self.pieces
+            .iter()
+            .enumerate()
+            .filter(|(_, p)| **p == piece)
+            .map(|(index, _)| (index / cols, index % cols))
+            .collect()
+    }
+
+    // 駒が取られて空きになったマスの一覧（行優先）
+    pub fn empty_cells(&self) -> Vec<(usize, usize)> {
+        self.find_all(Piece::Empty)
+    }
+
+    // 空きでないマスの割合（クロスチップのマスも埋まっているものとして数える）
+    pub fn fill_ratio(&self) -> f32 {
+        let filled = self
+            .pieces
+            .iter()
+            .filter(|piece| **piece != Piece::Empty)
+            .count();
+        filled as f32 / self.pieces.len() as f32
+    }
+
+    // fill_ratio から判定したラウンドの局面
+    pub fn game_phase(&self) -> GamePhase {
+        let ratio = self.fill_ratio();
+        if ratio >= OPENING_FILL_RATIO {
+            GamePhase::Opening
+        } else if ratio <= END_FILL_RATIO {
+            GamePhase::End
+        } else {
+            GamePhase::Mid
+        }
+    }
+
+    // 盤面に残っている数値ごとの個数（クロスと空きマスは数えない）
+    pub fn piece_counts(&self) -> BTreeMap<i32, usize> {
+        let mut counts = BTreeMap::new();
+        for value in self.pieces.iter().filter_map(|piece| piece.value()) {
+            *counts.entry(value).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    // 残っている駒が負の数だけか（駒が1つも残っていない場合はfalse）
+    pub fn only_negatives_remain(&self) -> bool {
+        let values: Vec<i32> = self
+            .pieces
+            .iter()
+            .filter_map(|piece| piece.value())
+            .collect();
+        !values.is_empty() && values.iter().all(|value| *value < 0)
+    }
+
+    // 盤面に残っている正の数値の合計
+    pub fn remaining_positive(&self) -> i32 {
+        self.pieces
+            .iter()
+            .filter_map(|piece| piece.value())
+            .filter(|value| *value > 0)
+            .sum()
+    }
+
+    // 移動を適用した新しい盤面と取得した駒を返す（selfは変更しない）
+    pub fn clone_with_move(
+        &self,
+        player: Player,
+        target: (usize, usize),
+    ) -> Result<(Board, Piece), MoveError> {
+        let mut board = self.clone();
+        let piece = board.make_move(player, target)?;
+        Ok((board, piece))
+    }
+
+    // UIのプレビュー用に、移動を適用した後の盤面だけを返す（selfは変更しない）
+    pub fn preview_after(
+        &self,
+        player: Player,
+        target: (usize, usize),
+    ) -> Result<Board, MoveError> {
+        self.clone_with_move(player, target).map(|(board, _)| board)
+    }
+
     // ゲームが終了したかチェック
     pub fn is_game_over(&self) -> bool {
+        self.occupancy() == 0
+    }
+
+    // 盤面を表示（デバッグ用）
+    pub fn display(&self) -> String {
         let (rows, cols) = self.size.dimensions();
+        let mut result = String::new();
 
         for row in 0..rows {
             for col in 0..cols {
-                if self.pieces[row][col] != Piece::Empty && self.pieces[row][col] != Piece::Cross {
-                    return false;
-                }
+                result.push_str(&format!("{} ", self.pieces[self.index(row, col)]));
             }
+            result.push('\n');
         }
 
-        true
+        result
     }
 
-    // 盤面を表示（デバッグ用）
-    pub fn display(&self) -> String {
+    // 移動先の表記（行・列は1始まり、例: "r1c3"）
+    pub fn move_notation(&self, target: (usize, usize)) -> String {
+        format!("r{}c{}", target.0 + 1, target.1 + 1)
+    }
+
+    // 盤面を数値の2次元配列に変換（機械学習の入力用）
+    // 数値の駒はその値、クロスは MATRIX_CROSS、空きマスは MATRIX_EMPTY、特殊マスは0
+    pub fn to_matrix(&self) -> Vec<Vec<i32>> {
+        self.to_matrix_with(MATRIX_CROSS, MATRIX_EMPTY)
+    }
+
+    // クロスと空きマスの値を指定して数値の2次元配列に変換
+    pub fn to_matrix_with(&self, cross_val: i32, empty_val: i32) -> Vec<Vec<i32>> {
+        let (rows, cols) = self.size.dimensions();
+        (0..rows)
+            .map(|row| {
+                (0..cols)
+                    .map(|col| match self.pieces[self.index(row, col)] {
+                        Piece::Number(n) => n,
+                        Piece::Cross => cross_val,
+                        Piece::Empty => empty_val,
+                        Piece::Bonus(_) => 0,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    // ASCII形式で盤面を出力（X = クロス、. = 空き、+ = 特殊マス、数値 = 駒）
+    // from_ascii で読み戻せる
+    pub fn to_ascii(&self) -> String {
         let (rows, cols) = self.size.dimensions();
         let mut result = String::new();
 
         for row in 0..rows {
-            for col in 0..cols {
-                result.push_str(&format!("{} ", self.pieces[row][col]));
-            }
+            let tokens: Vec<String> = (0..cols)
+                .map(|col| match self.pieces[self.index(row, col)] {
+                    Piece::Number(n) => format!("{:>3}", n),
+                    Piece::Cross => "  X".to_string(),
+                    Piece::Empty => "  .".to_string(),
+                    Piece::Bonus(BonusKind::ExtraTurn) => "  +".to_string(),
+                })
+                .collect();
+            result.push_str(&tokens.join(" "));
             result.push('\n');
         }
 
         result
     }
 
+    // ASCII形式の盤面を読み込む（パズル用）
+    // 空白区切りのトークン: X = クロス、. = 空き、+ = もう一度移動できる特殊マス、符号付き整数 = 駒
+    // 空行は無視し、行数・列数から盤面サイズを決める
+    pub fn from_ascii(text: &str) -> Result<Board, BoardError> {
+        let mut grid: Vec<Vec<Piece>> = Vec::new();
+
+        for line in text.lines().filter(|line| !line.trim().is_empty()) {
+            let row = line
+                .split_whitespace()
+                .map(|token| match token {
+                    "X" | "x" => Ok(Piece::Cross),
+                    "." => Ok(Piece::Empty),
+                    "+" => Ok(Piece::Bonus(BonusKind::ExtraTurn)),
+                    _ => token
+                        .parse::<i32>()
+                        .map(Piece::Number)
+                        .map_err(|_| BoardError::InvalidToken(token.to_string())),
+                })
+                .collect::<Result<Vec<Piece>, BoardError>>()?;
+
+            if let Some(first) = grid.first() {
+                if first.len() != row.len() {
+                    return Err(BoardError::RaggedRow(grid.len()));
+                }
+            }
+            grid.push(row);
+        }
+
+        let rows = grid.len();
+        let cols = grid.first().map_or(0, |row| row.len());
+        let size = BoardSize::from_dimensions(rows, cols)
+            .ok_or(BoardError::UnsupportedSize(rows, cols))?;
+
+        let crosses: Vec<(usize, usize)> = (0..rows)
+            .flat_map(|r| (0..cols).map(move |c| (r, c)))
+            .filter(|&(r, c)| grid[r][c] == Piece::Cross)
+            .collect();
+        if crosses.len() != 1 {
+            return Err(BoardError::CrossCount(crosses.len()));
+        }
+
+        Ok(Board {
+            size,
+            pieces: grid.into_iter().flatten().collect(),
+            cross_position: crosses[0],
+            rules: Rules::default(),
+        })
+    }
+
+    // 途中まで駒が取られた盤面からパズルを作成（from_ascii と同じ形式）
+    // クロスはちょうど1つ必要だが、空きマスはいくつあってもよい
+    pub fn new_puzzle(ascii: &str) -> Result<Board, BoardError> {
+        Board::from_ascii(ascii)
+    }
+
+    // 読み上げ用に盤面を文章で説明（行・列は1始まり）
+    // 例: "Cross at row 2, column 3. Row 2 contains: 5, empty, cross, -1."
+    pub fn describe(&self) -> String {
+        let (rows, cols) = self.size.dimensions();
+        let (row, col) = self.cross_position;
+
+        let describe_piece = |piece: Piece| match piece {
+            Piece::Number(n) => n.to_string(),
+            Piece::Cross => "cross".to_string(),
+            Piece::Empty => "empty".to_string(),
+            Piece::Bonus(BonusKind::ExtraTurn) => "extra turn".to_string(),
+        };
+        let row_contents: Vec<String> = (0..cols)
+            .map(|c| describe_piece(self.get_piece(row, c)))
+            .collect();
+        let column_contents: Vec<String> = (0..rows)
+            .map(|r| describe_piece(self.get_piece(r, col)))
+            .collect();
+
+        format!(
+            "Cross at row {}, column {}. Row {} contains: {}. Column {} contains: {}.",
+            row + 1,
+            col + 1,
+            row + 1,
+            row_contents.join(", "),
+            col + 1,
+            column_contents.join(", ")
+        )
+    }
+
+    // 盤面内のマスか
+    fn contains(&self, row: usize, col: usize) -> bool {
+        let (rows, cols) = self.size.dimensions();
+        row < rows && col < cols
+    }
+
+    // マスの位置から pieces の添字を求める
+    fn index(&self, row: usize, col: usize) -> usize {
+        row * self.size.dimensions().1 + col
+    }
+
     // 特定の位置の駒を取得
     pub fn get_piece(&self, row: usize, col: usize) -> Piece {
-        if row < self.pieces.len() && col < self.pieces[0].len() {
-            self.pieces[row][col]
+        if self.contains(row, col) {
+            self.pieces[self.index(row, col)]
         } else {
             Piece::Empty
         }
@@ -291,8 +1111,9 @@ impl Board {
 
     // 特定の位置に駒を設定（テスト用）
     pub fn set_piece(&mut self, row: usize, col: usize, piece: Piece) {
-        if row < self.pieces.len() && col < self.pieces[0].len() {
-            self.pieces[row][col] = piece;
+        if self.contains(row, col) {
+            let index = self.index(row, col);
+            self.pieces[index] = piece;
             if piece == Piece::Cross {
                 self.cross_position = (row, col);
             }
@@ -300,7 +1121,23 @@ impl Board {
     }
 }
 
-#[cfg(test)]
+// マスと駒の組み合わせごとの固定の乱数（空きマスは0）
+// 駒の数値に上限がないため、表の代わりにSplitMix64で値を作る
+fn zobrist_key(index: usize, piece: Piece) -> u64 {
+    let code = match piece {
+        Piece::Empty => return 0,
+        Piece::Cross => 1,
+        Piece::Bonus(BonusKind::ExtraTurn) => 2,
+        Piece::Number(n) => 3 + n as u32 as u64,
+    };
+
+    let mut z = ((index as u64) << 40 ^ code).wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(all(test, feature = "rand"))]
 mod tests {
     use super::*;
     use crate::game::*;
@@ -360,26 +1197,80 @@ mod tests {
         assert_eq!(format!("{}", Piece::Empty), "   ");
     }
 
+    #[test]
+    fn test_from_ascii_round_trip() {
+        // 空きマスを含む盤面は to_ascii で往復できる
+        let mut board = Board::new_seeded(BoardSize::Small, 11);
+        board.set_piece(3, 3, Piece::Empty);
+        let parsed = Board::from_ascii(&board.to_ascii()).unwrap();
+        assert_eq!(parsed.size, board.size);
+        assert_eq!(parsed.pieces, board.pieces);
+        assert_eq!(parsed.cross_position, board.cross_position);
+
+        // 空きマスのない盤面は display の出力もそのまま読める
+        let board = Board::new_seeded(BoardSize::Large, 11);
+        let parsed = Board::from_ascii(&board.display()).unwrap();
+        assert_eq!(parsed.size, BoardSize::Large);
+        assert_eq!(parsed.pieces, board.pieces);
+        assert_eq!(parsed.cross_position, board.cross_position);
+    }
+
+    #[test]
+    fn test_from_ascii_errors() {
+        let ragged = "X 1 2 3\n4 5 6\n7 8 9 10\n. . . .\n";
+        assert_eq!(
+            Board::from_ascii(ragged).unwrap_err(),
+            BoardError::RaggedRow(1)
+        );
+
+        let no_cross = "0 1 2 3\n4 5 6 7\n8 9 10 11\n. . . .\n";
+        assert_eq!(
+            Board::from_ascii(no_cross).unwrap_err(),
+            BoardError::CrossCount(0)
+        );
+
+        let bad_token = "X 1 2 q\n4 5 6 7\n8 9 10 11\n. . . .\n";
+        assert_eq!(
+            Board::from_ascii(bad_token).unwrap_err(),
+            BoardError::InvalidToken("q".to_string())
+        );
+
+        assert_eq!(
+            Board::from_ascii("X 1 2\n3 4 5\n6 7 8\n").unwrap_err(),
+            BoardError::UnsupportedSize(3, 3)
+        );
+    }
+
+    #[test]
+    fn test_piece_value() {
+        assert_eq!(Piece::Number(5).value(), Some(5));
+        assert_eq!(Piece::Number(-3).value(), Some(-3));
+        assert_eq!(Piece::Cross.value(), None);
+        assert_eq!(Piece::Empty.value(), None);
+
+        assert!(Piece::Number(0).is_scoring());
+        assert!(!Piece::Cross.is_scoring());
+        assert!(!Piece::Empty.is_scoring());
+    }
+
     #[test]
     fn test_board_initialization() {
         let board = Board::new(BoardSize::Small);
 
         // 4x4ボードのサイズを確認
-        assert_eq!(board.pieces.len(), 4);
-        assert_eq!(board.pieces[0].len(), 4);
+        assert_eq!(board.pieces.len(), 16);
 
         // 駒の総数を確認
         let mut num_count = 0;
         let mut cross_count = 0;
         let mut empty_count = 0;
 
-        for row in &board.pieces {
-            for piece in row {
-                match piece {
-                    Piece::Number(_) => num_count += 1,
-                    Piece::Cross => cross_count += 1,
-                    Piece::Empty => empty_count += 1,
-                }
+        for piece in &board.pieces {
+            match piece {
+                Piece::Number(_) => num_count += 1,
+                Piece::Cross => cross_count += 1,
+                Piece::Empty => empty_count += 1,
+                Piece::Bonus(_) => {}
             }
         }
 
@@ -388,13 +1279,298 @@ mod tests {
         assert_eq!(empty_count, 0); // 空きマスはない（初期状態）
     }
 
+    #[test]
+    fn test_slide_to_first_movement() {
+        let board = Board::from_ascii(
+            "1 . 2 . . 3\n. . . . . .\n4 5 . X . 6\n. . . 7 . .\n. . . . . .\n. . . 8 . .\n",
+        )
+        .unwrap();
+
+        // 空きマスは飛び越えるが、その先の駒は飛び越えない
+        assert_eq!(
+            board.get_valid_moves_with(Player::First, Movement::SlideToFirst),
+            vec![(2, 1), (2, 5)]
+        );
+        assert_eq!(
+            board.get_valid_moves_with(Player::Second, Movement::SlideToFirst),
+            vec![(3, 3)]
+        );
+        assert_eq!(
+            board.get_valid_moves_with(Player::First, Movement::Jump),
+            vec![(2, 0), (2, 1), (2, 5)]
+        );
+    }
+
+    #[test]
+    fn test_move_direction_as_map_key() {
+        use std::collections::HashMap;
+
+        let mut moves_per_direction: HashMap<MoveDirection, usize> = HashMap::new();
+        for player in [Player::First, Player::Second, Player::Third] {
+            *moves_per_direction.entry(player.direction()).or_default() += 1;
+        }
+
+        assert_eq!(moves_per_direction[&MoveDirection::Horizontal], 2);
+        assert_eq!(moves_per_direction[&MoveDirection::Vertical], 1);
+    }
+
+    #[test]
+    fn test_axis_neighbors() {
+        let board = Board::from_ascii(
+            "1 . 2 . . 3\n. . . . . .\n4 5 . X . 6\n. . . 7 . .\n. . . . . .\n. . . 8 . .\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            board.axis_neighbors(Player::First),
+            (
+                Some((2, 1, Piece::Number(5))),
+                Some((2, 5, Piece::Number(6)))
+            )
+        );
+        // 上側には駒がない
+        assert_eq!(
+            board.axis_neighbors(Player::Second),
+            (None, Some((3, 3, Piece::Number(7))))
+        );
+    }
+
+    #[test]
+    fn test_with_cross_at_out_of_bounds() {
+        let board = Board::with_cross_at(BoardSize::Large, 3, (5, 5)).unwrap();
+        assert_eq!(board.cross_position, (5, 5));
+        assert_eq!(board.get_piece(5, 5), Piece::Cross);
+
+        // 行優先の添字では有効な位置になる列も盤面の外として拒否する
+        assert_eq!(
+            Board::with_cross_at(BoardSize::Large, 3, (0, 6)),
+            Err(BoardError::OutOfBounds((0, 6)))
+        );
+        assert_eq!(
+            Board::with_cross_at(BoardSize::Small, 3, (4, 0)),
+            Err(BoardError::OutOfBounds((4, 0)))
+        );
+    }
+
+    #[test]
+    fn test_valid_moves_order() {
+        let mut board = Board::with_cross_at(BoardSize::Large, 3, (2, 3)).unwrap();
+        board.set_piece(2, 0, Piece::Empty);
+        board.set_piece(4, 3, Piece::Empty);
+
+        // 横方向は列の昇順
+        assert_eq!(
+            board.get_valid_moves(Player::First),
+            vec![(2, 1), (2, 2), (2, 4), (2, 5)]
+        );
+        // 縦方向は行の昇順
+        assert_eq!(
+            board.get_valid_moves(Player::Second),
+            vec![(0, 3), (1, 3), (3, 3), (5, 3)]
+        );
+    }
+
+    #[test]
+    fn test_negative_capture_forbidden() {
+        let mut board = Board::with_cross_at(BoardSize::Large, 5, (2, 2)).unwrap();
+        for col in 0..6 {
+            if col != 2 {
+                board.set_piece(2, col, Piece::Number(col as i32 - 3));
+            }
+        }
+
+        // 既定では負の駒も取得できる
+        assert_eq!(board.get_valid_moves(Player::First).len(), 5);
+
+        board.rules.allow_negative_capture = false;
+        assert_eq!(
+            board.get_valid_moves(Player::First),
+            vec![(2, 3), (2, 4), (2, 5)]
+        );
+        assert_eq!(
+            board.make_move(Player::First, (2, 0)),
+            Err(MoveError::NegativeForbidden((2, 0)))
+        );
+        assert_eq!(board.make_move(Player::First, (2, 4)), Ok(Piece::Number(1)));
+    }
+
+    #[test]
+    fn test_move_notation() {
+        let board = Board::new_seeded(BoardSize::Small, 1);
+        assert_eq!(board.move_notation((0, 2)), "r1c3");
+        assert_eq!(board.move_notation((3, 0)), "r4c1");
+    }
+
+    #[test]
+    fn test_count_nonempty_in_row_and_column() {
+        let board = Board::from_ascii(
+            "X 1 . 3\n\
+             . . 2 .\n\
+             4 . 5 6\n\
+             . 7 . .\n",
+        )
+        .unwrap();
+
+        // クロスチップは数えない
+        assert_eq!(board.count_nonempty_in_row(0), 2);
+        assert_eq!(board.count_nonempty_in_row(1), 1);
+        assert_eq!(board.count_nonempty_in_row(2), 3);
+        assert_eq!(board.count_nonempty_in_column(0), 1);
+        assert_eq!(board.count_nonempty_in_column(2), 2);
+        assert_eq!(board.count_nonempty_in_row(9), 0);
+    }
+
+    #[test]
+    fn test_same_numbers() {
+        let board = Board::from_ascii("X 1 . 3\n. . 2 .\n4 . 5 6\n. 7 . .\n").unwrap();
+        let moved = Board::from_ascii(". 1 . 3\n. X 2 .\n4 . 5 6\n. 7 . .\n").unwrap();
+
+        assert!(board.same_numbers(&moved));
+        assert_ne!(board, moved);
+        assert_eq!(board, board.clone());
+
+        let different = Board::from_ascii("X 1 . 3\n. . 2 .\n4 . 5 6\n. 8 . .\n").unwrap();
+        assert!(!board.same_numbers(&different));
+    }
+
+    #[test]
+    fn test_empty_cells() {
+        let mut board = Board::new_seeded(BoardSize::Small, 5);
+        assert!(board.empty_cells().is_empty());
+
+        let from = board.cross_position;
+        let target = board.get_valid_moves(Player::First)[0];
+        board.make_move(Player::First, target).unwrap();
+        assert_eq!(board.empty_cells(), vec![from]);
+    }
+
+    #[test]
+    fn test_zobrist_hash() {
+        let board = Board::new_seeded(BoardSize::Small, 11);
+        assert_eq!(board.zobrist_hash(), board.clone().zobrist_hash());
+
+        let mut moved = board.clone();
+        let target = moved.get_valid_moves(Player::First)[0];
+        moved.make_move(Player::First, target).unwrap();
+        assert_ne!(board.zobrist_hash(), moved.zobrist_hash());
+    }
+
+    #[test]
+    fn test_new_balanced() {
+        let board = Board::new_balanced(BoardSize::Large, 3, 40.0).unwrap();
+        assert!(board.balance_score() <= 40.0);
+        assert_eq!(Board::new_balanced(BoardSize::Large, 3, 40.0), Ok(board));
+
+        // 全ての行と列の合計が等しくなる配置はまれで、試行回数内には見つからない
+        assert_eq!(
+            Board::new_balanced(BoardSize::Small, 3, 0.0),
+            Err(BoardError::Unbalanced(BALANCE_ATTEMPTS))
+        );
+    }
+
+    #[test]
+    fn test_to_matrix() {
+        let mut board = Board::new_seeded(BoardSize::Large, 6);
+        let (row, col) = board.cross_position;
+        let target = board.get_valid_moves(Player::First)[0];
+        board.make_move(Player::First, target).unwrap();
+
+        let matrix = board.to_matrix();
+        assert_eq!(matrix.len(), 6);
+        assert!(matrix.iter().all(|line| line.len() == 6));
+        assert_eq!(matrix[target.0][target.1], MATRIX_CROSS);
+        assert_eq!(matrix[row][col], MATRIX_EMPTY);
+
+        let custom = board.to_matrix_with(99, -99);
+        assert_eq!(custom[target.0][target.1], 99);
+        assert_eq!(custom[row][col], -99);
+    }
+
+    #[test]
+    fn test_find_piece() {
+        let board = Board::new(BoardSize::Small);
+        assert_eq!(board.find_piece(Piece::Cross), Some(board.cross_position));
+
+        // 8は1個、1～7は2個ずつ
+        assert_eq!(board.find_all(Piece::Number(8)).len(), 1);
+        assert_eq!(board.find_all(Piece::Number(3)).len(), 2);
+        assert_eq!(board.find_piece(Piece::Number(-1)), None);
+        assert_eq!(board.find_piece(Piece::Empty), None);
+    }
+
+    #[test]
+    fn test_daily_board() {
+        let today = Board::daily(BoardSize::Small, (2024, 3, 9));
+        assert_eq!(today, Board::daily(BoardSize::Small, (2024, 3, 9)));
+        assert_eq!(today, Board::new_seeded(BoardSize::Small, 20240309));
+        assert_ne!(today, Board::daily(BoardSize::Small, (2024, 3, 10)));
+    }
+
+    #[test]
+    fn test_only_negatives_remain() {
+        let mut board = Board::from_ascii(
+            ". . . . . .\n\
+             . -3 . . . .\n\
+             . . X . -1 .\n\
+             . . . . . .\n\
+             -10 . . . . .\n\
+             . . . . . 2\n",
+        )
+        .unwrap();
+        assert!(!board.only_negatives_remain());
+
+        board.set_piece(5, 5, Piece::Empty);
+        assert!(board.only_negatives_remain());
+
+        // 駒が残っていなければfalse
+        for (row, col) in board.find_all(Piece::Number(-3)) {
+            board.set_piece(row, col, Piece::Empty);
+        }
+        board.set_piece(2, 4, Piece::Empty);
+        board.set_piece(4, 0, Piece::Empty);
+        assert!(!board.only_negatives_remain());
+    }
+
+    #[test]
+    fn test_flat_layout_is_row_major() {
+        let mut board = Board::new_seeded(BoardSize::Large, 2);
+        board.set_piece(2, 4, Piece::Number(42));
+
+        // 添字は row * cols + col
+        assert_eq!(board.pieces[2 * 6 + 4], Piece::Number(42));
+        assert_eq!(board.get_piece(2, 4), Piece::Number(42));
+        let (row, col) = board.cross_position;
+        assert_eq!(board.pieces[row * 6 + col], Piece::Cross);
+
+        // 盤面外は無視される
+        board.set_piece(6, 0, Piece::Number(1));
+        assert_eq!(board.get_piece(6, 0), Piece::Empty);
+        assert_eq!(board.get_piece(0, 6), Piece::Empty);
+    }
+
+    #[test]
+    fn test_clone_many_boards() {
+        let board = Board::new_seeded(BoardSize::Large, 8);
+        let clones: Vec<Board> = (0..10_000).map(|_| board.clone()).collect();
+        assert!(clones.iter().all(|clone| *clone == board));
+    }
+
+    #[test]
+    fn test_piece_counts() {
+        let board = Board::new(BoardSize::Small);
+
+        // 1～7を各2個、8を1個
+        let mut expected: BTreeMap<i32, usize> = (1..=7).map(|i| (i, 2)).collect();
+        expected.insert(8, 1);
+        assert_eq!(board.piece_counts(), expected);
+    }
+
     #[test]
     fn test_large_board_initialization() {
         let board = Board::new(BoardSize::Large);
 
         // 6x6ボードのサイズを確認
-        assert_eq!(board.pieces.len(), 6);
-        assert_eq!(board.pieces[0].len(), 6);
+        assert_eq!(board.pieces.len(), 36);
 
         // 駒の総数を確認
         let mut positive_count = 0;
@@ -402,15 +1578,13 @@ mod tests {
         let mut cross_count = 0;
         let mut empty_count = 0;
 
-        for row in &board.pieces {
-            for piece in row {
-                match piece {
-                    Piece::Number(n) if *n > 0 => positive_count += 1,
-                    Piece::Number(n) if *n < 0 => negative_count += 1,
-                    Piece::Cross => cross_count += 1,
-                    Piece::Empty => empty_count += 1,
-                    _ => {}
-                }
+        for piece in &board.pieces {
+            match piece {
+                Piece::Number(n) if *n > 0 => positive_count += 1,
+                Piece::Number(n) if *n < 0 => negative_count += 1,
+                Piece::Cross => cross_count += 1,
+                Piece::Empty => empty_count += 1,
+                _ => {}
             }
         }
 
@@ -427,6 +1601,99 @@ mod tests {
         assert!(negative_count > 0, "There should be negative numbers");
     }
 
+    #[test]
+    fn test_board_center() {
+        // 偶数サイズでは左上寄りのマスを中央とする
+        assert_eq!(BoardSize::Small.center(), (1, 1));
+        assert_eq!(BoardSize::Large.center(), (2, 2));
+
+        let board = Board::new_cross_centered(BoardSize::Small, 42);
+        assert_eq!(board.cross_position, (1, 1));
+        assert_eq!(board.get_piece(1, 1), Piece::Cross);
+        assert_eq!(board.occupancy().count_ones(), 15);
+    }
+
+    #[test]
+    fn test_new_seeded_is_reproducible() {
+        let a = Board::new_seeded(BoardSize::Large, 7);
+        let b = Board::new_seeded(BoardSize::Large, 7);
+        assert_eq!(a.pieces, b.pieces);
+        assert_eq!(a.cross_position, b.cross_position);
+    }
+
+    #[test]
+    fn test_place_pieces_with_seed() {
+        let mut set: Vec<Piece> = (1..=15).map(Piece::Number).collect();
+        set.push(Piece::Cross);
+
+        let mut a = Board::new(BoardSize::Small);
+        a.place_pieces(set.clone(), &mut StdRng::seed_from_u64(3));
+        let mut b = Board::new(BoardSize::Small);
+        b.place_pieces(set, &mut StdRng::seed_from_u64(3));
+
+        // 同じシードなら同じ配置になる
+        assert_eq!(a.pieces, b.pieces);
+        assert_eq!(a.cross_position, b.cross_position);
+        assert_eq!(
+            a.get_piece(a.cross_position.0, a.cross_position.1),
+            Piece::Cross
+        );
+
+        // 指定した駒がすべて配置されている
+        let mut values: Vec<i32> = a
+            .pieces
+            .iter()
+            .filter_map(|piece| match piece {
+                Piece::Number(n) => Some(*n),
+                _ => None,
+            })
+            .collect();
+        values.sort();
+        assert_eq!(values, (1..=15).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_place_pieces_wrong_count() {
+        let mut board = Board::new(BoardSize::Small);
+        board.place_pieces(vec![Piece::Cross], &mut StdRng::seed_from_u64(0));
+    }
+
+    #[test]
+    fn test_describe() {
+        let mut board = Board::new(BoardSize::Small);
+        for row in 0..4 {
+            for col in 0..4 {
+                board.set_piece(row, col, Piece::Empty);
+            }
+        }
+        board.set_piece(1, 2, Piece::Cross);
+        board.set_piece(1, 0, Piece::Number(5));
+        board.set_piece(1, 3, Piece::Number(-1));
+
+        let description = board.describe();
+        assert!(description.starts_with("Cross at row 2, column 3."));
+        assert!(description.contains("Row 2 contains: 5, empty, cross, -1."));
+        assert!(description.contains("Column 3 contains: empty, cross, empty, empty."));
+    }
+
+    #[test]
+    fn test_line_cells() {
+        let board = Board::new_seeded(BoardSize::Large, 11);
+        let (row, col) = board.cross_position;
+
+        // 横方向のプレイヤーはクロスチップの行の全マス
+        let cells: Vec<_> = board.line_cells(Player::First).collect();
+        assert_eq!(cells.len(), 6);
+        assert!(cells.iter().all(|&(r, _, _)| r == row));
+        assert!(cells.contains(&(row, col, Piece::Cross)));
+
+        // 縦方向のプレイヤーはクロスチップの列の全マス
+        let cells: Vec<_> = board.line_cells(Player::Second).collect();
+        assert_eq!(cells.len(), 6);
+        assert!(cells.iter().all(|&(_, c, _)| c == col));
+    }
+
     #[test]
     fn test_valid_moves() {
         let mut board = Board::new(BoardSize::Small);
@@ -436,9 +1703,9 @@ mod tests {
         for row in 0..4 {
             for col in 0..4 {
                 if (row, col) == (1, 2) {
-                    board.pieces[row][col] = Piece::Cross;
+                    board.set_piece(row, col, Piece::Cross);
                 } else {
-                    board.pieces[row][col] = Piece::Number(1);
+                    board.set_piece(row, col, Piece::Number(1));
                 }
             }
         }
@@ -467,15 +1734,15 @@ mod tests {
         for row in 0..4 {
             for col in 0..4 {
                 if (row, col) == (1, 2) {
-                    board.pieces[row][col] = Piece::Cross;
+                    board.set_piece(row, col, Piece::Cross);
                 } else {
-                    board.pieces[row][col] = Piece::Number(1);
+                    board.set_piece(row, col, Piece::Number(1));
                 }
             }
         }
 
         // 特定の位置に特別な値を設定
-        board.pieces[1][3] = Piece::Number(5);
+        board.set_piece(1, 3, Piece::Number(5));
 
         // 移動を実行
         let result = board.make_move(Player::First, (1, 3));
@@ -486,8 +1753,61 @@ mod tests {
 
         // クロスチップが移動していることを確認
         assert_eq!(board.cross_position, (1, 3));
-        assert_eq!(board.pieces[1][3], Piece::Cross);
-        assert_eq!(board.pieces[1][2], Piece::Empty);
+        assert_eq!(board.get_piece(1, 3), Piece::Cross);
+        assert_eq!(board.get_piece(1, 2), Piece::Empty);
+    }
+
+    #[test]
+    fn test_clone_with_move() {
+        let mut board = Board::new(BoardSize::Small);
+
+        // クロスチップの位置を固定
+        board.cross_position = (1, 2);
+        for row in 0..4 {
+            for col in 0..4 {
+                if (row, col) == (1, 2) {
+                    board.set_piece(row, col, Piece::Cross);
+                } else {
+                    board.set_piece(row, col, Piece::Number(1));
+                }
+            }
+        }
+        board.set_piece(1, 3, Piece::Number(5));
+        let original = board.pieces.clone();
+
+        let (moved, piece) = board.clone_with_move(Player::First, (1, 3)).unwrap();
+
+        // 元の盤面は変わらない
+        assert_eq!(board.pieces, original);
+        assert_eq!(board.cross_position, (1, 2));
+
+        // 新しい盤面には移動が反映されている
+        assert_eq!(piece, Piece::Number(5));
+        assert_eq!(moved.cross_position, (1, 3));
+        assert_eq!(moved.get_piece(1, 3), Piece::Cross);
+        assert_eq!(moved.get_piece(1, 2), Piece::Empty);
+
+        // 無効な移動はエラー
+        assert_eq!(
+            board.clone_with_move(Player::First, (2, 3)).unwrap_err(),
+            MoveError::InvalidTarget((2, 3))
+        );
+    }
+
+    #[test]
+    fn test_move_to_cross_position() {
+        let mut board = Board::new_seeded(BoardSize::Small, 2);
+        let cross = board.cross_position;
+
+        assert_eq!(
+            board.make_move(Player::First, cross),
+            Err(MoveError::IsCrossPosition(cross))
+        );
+        assert_eq!(
+            board.make_move(Player::Second, cross),
+            Err(MoveError::IsCrossPosition(cross))
+        );
+        assert_eq!(board.cross_position, cross);
     }
 
     #[test]
@@ -499,9 +1819,9 @@ mod tests {
         for row in 0..4 {
             for col in 0..4 {
                 if (row, col) == (1, 2) {
-                    board.pieces[row][col] = Piece::Cross;
+                    board.set_piece(row, col, Piece::Cross);
                 } else {
-                    board.pieces[row][col] = Piece::Number(1);
+                    board.set_piece(row, col, Piece::Number(1));
                 }
             }
         }
@@ -519,6 +1839,55 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_is_valid_target() {
+        let mut board = Board::new(BoardSize::Small);
+
+        // クロスチップの位置を固定
+        board.cross_position = (1, 2);
+        for row in 0..4 {
+            for col in 0..4 {
+                if (row, col) == (1, 2) {
+                    board.set_piece(row, col, Piece::Cross);
+                } else {
+                    board.set_piece(row, col, Piece::Number(1));
+                }
+            }
+        }
+        board.set_piece(1, 0, Piece::Empty);
+
+        // 同じ行の駒がある位置
+        assert!(board.is_valid_target(Player::First, (1, 3)));
+        // 同じ行だが空きマス
+        assert!(!board.is_valid_target(Player::First, (1, 0)));
+        // クロスチップの位置
+        assert!(!board.is_valid_target(Player::First, (1, 2)));
+        // 行が異なる位置
+        assert!(!board.is_valid_target(Player::First, (2, 3)));
+
+        assert_eq!(
+            board.peek_capture(Player::First, (1, 3)),
+            Some(Piece::Number(1))
+        );
+        assert_eq!(board.peek_capture(Player::First, (2, 3)), None);
+    }
+
+    #[test]
+    fn test_occupancy() {
+        let mut board = Board::new(BoardSize::Small);
+
+        // 初期状態ではクロスチップ以外の15マスが埋まっている
+        assert_eq!(board.occupancy().count_ones(), 15);
+        let cross_bit = 1 << (board.cross_position.0 * 4 + board.cross_position.1);
+        assert_eq!(board.occupancy() & cross_bit, 0);
+
+        // 1手進めると1つ減り、移動先のビットが消える
+        let target = board.get_valid_moves(Player::First)[0];
+        board.make_move(Player::First, target).unwrap();
+        assert_eq!(board.occupancy().count_ones(), 14);
+        assert_eq!(board.occupancy() & (1 << (target.0 * 4 + target.1)), 0);
+    }
+
     #[test]
     fn test_game_over() {
         let mut board = Board::new(BoardSize::Small);
@@ -526,19 +1895,19 @@ mod tests {
         // すべてのマスを空にする
         for row in 0..4 {
             for col in 0..4 {
-                board.pieces[row][col] = Piece::Empty;
+                board.set_piece(row, col, Piece::Empty);
             }
         }
 
         // クロスチップだけを配置
-        board.pieces[1][2] = Piece::Cross;
+        board.set_piece(1, 2, Piece::Cross);
         board.cross_position = (1, 2);
 
         // ゲームが終了していることを確認
         assert!(board.is_game_over());
 
         // 1つ数値駒を追加
-        board.pieces[0][0] = Piece::Number(3);
+        board.set_piece(0, 0, Piece::Number(3));
 
         // ゲームがまだ終了していないことを確認
         assert!(!board.is_game_over());
@@ -559,3 +1928,23 @@ mod tests {
         assert_eq!(four_players[3], Player::Fourth);
     }
 }
+
+// rand フィーチャーなし（thread_rng を使わない）でもシード指定で遊べることの確認
+#[cfg(all(test, not(feature = "rand")))]
+mod seeded_tests {
+    use super::*;
+    use crate::game::GameSession;
+
+    #[test]
+    fn test_seeded_board_without_thread_rng() {
+        let board = Board::new_seeded(BoardSize::Small, 9);
+        assert_eq!(board, Board::new_seeded(BoardSize::Small, 9));
+
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        let target = session.valid_moves(Player::First)[0];
+        session.process_move(target).unwrap();
+        session.start_next_round();
+        assert_eq!(session.round, 2);
+        assert!(session.board.find_piece(Piece::Cross).is_some());
+    }
+}
@@ -1,6 +1,69 @@
 // src/game.rs - ゲームセッション管理
-use crate::core::{Board, BoardSize, GameMode, Piece, Player};
+#[cfg(feature = "rand")]
+use crate::core::BoardSize;
+use crate::core::{
+    Board, BoardGenerator, BonusKind, DefaultGenerator, GameMode, MoveDirection, MoveError, Piece,
+    Player,
+};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::fmt;
+use std::rc::Rc;
+use std::sync::mpsc::{self, Receiver, Sender};
+
+// ゲーム進行状態に関するエラー
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StateError {
+    GameInProgress,    // すでにゲームが開始されている
+    RoundNotOver,      // ラウンドがまだ終了していない
+    SwapNotAllowed,    // サイドの交換ができるのは2人モードの後手の最初の手番のみ
+    PlayerMismatch,    // 指定したプレイヤーがゲームモードの参加者と一致しない
+    NotInGame(Player), // ゲームに参加していないプレイヤー
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StateError::GameInProgress => write!(f, "the game is already in progress"),
+            StateError::RoundNotOver => write!(f, "the current round is not over yet"),
+            StateError::SwapNotAllowed => {
+                write!(f, "sides can only be swapped as Second's first action")
+            }
+            StateError::PlayerMismatch => {
+                write!(f, "players do not match the players of the game mode")
+            }
+            StateError::NotInGame(player) => write!(f, "{:?} is not in the game", player),
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+// イベントログからの再構築に関するエラー（イベントの番号は0始まり）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EventLogError {
+    IllegalMove(usize, MoveError), // 記録された手を適用できなかった
+    State(usize, StateError),      // 手番の設定やサイド交換を適用できなかった
+    MissingBoard(usize),           // 新しいラウンドの前に BoardGenerated がない
+}
+
+impl fmt::Display for EventLogError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventLogError::IllegalMove(index, e) => write!(f, "Event {}: {}", index, e),
+            EventLogError::State(index, e) => write!(f, "Event {}: {}", index, e),
+            EventLogError::MissingBoard(index) => {
+                write!(
+                    f,
+                    "Event {} starts a round without a generated board",
+                    index
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for EventLogError {}
 
 // プレイヤースコア
 #[derive(Debug, Clone, Default)]
@@ -18,155 +81,672 @@ impl PlayerScore {
     }
 
     pub fn add_piece(&mut self, piece: Piece) {
-        if let Piece::Number(value) = piece {
+        self.add_value(piece);
+        self.pieces.push(piece);
+    }
+
+    // 駒を記録せずに得点だけ加算
+    pub fn add_value(&mut self, piece: Piece) {
+        if let Some(value) = piece.value() {
             self.total += value;
         }
-        self.pieces.push(piece);
     }
 }
 
+// 1手分の記録
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveRecord {
+    pub player: Player,
+    pub from: (usize, usize), // 移動前のクロスチップの位置
+    pub target: (usize, usize),
+    pub piece: Piece,                         // 取得した駒
+    pub passed: Vec<((usize, usize), Piece)>, // Movement::Magnet で通過して取得した駒
+}
+
+// 完了したラウンドの結果
+#[derive(Debug, Clone)]
+pub struct RoundRecord {
+    pub winner: Option<Player>,
+    pub scores: HashMap<Player, PlayerScore>,
+    pub reason: RoundEndReason,
+}
+
+// ラウンドが終わった理由
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundEndReason {
+    BoardCleared, // 取得できる駒がなくなった
+    NoValidMoves, // 手番のプレイヤーに移動先がなくなった
+    Repetition,   // 同じ局面が3回現れて引き分けになった（repetition_draw が有効な場合）
+    Abandoned,    // 終了条件を満たす前に次のラウンドへ進んだ
+}
+
+// 1ラウンド分の結果（GameResult用）
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundResult {
+    pub winner: Option<Player>,
+    pub scores: HashMap<Player, i32>,
+    pub reason: RoundEndReason,
+}
+
+// ゲーム全体の結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameResult {
+    pub overall_winner: Option<Player>,
+    pub total_scores: HashMap<Player, i32>,
+    pub rounds: Vec<RoundResult>,
+}
+
+// 合計得点が同点の場合の総合勝者の決め方
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum TieBreak {
+    #[default]
+    None, // 引き分けとする
+    MostRoundsWon,   // 勝利ラウンド数が多いプレイヤー
+    FewestNegatives, // 取得した負の駒が少ないプレイヤー
+    HeadToHead,      // 同点のプレイヤー間で最高得点だったラウンドが多いプレイヤー
+}
+
+// 最善手とその理由（コーチング表示用）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MoveExplanation {
+    pub target: (usize, usize),
+    pub immediate_value: i32,         // この手で得られる値
+    pub denies_opponent: Option<i32>, // 他の手と比べて次のプレイヤーから奪った値
+}
+
 // ゲームセッション
 #[derive(Debug, Clone)]
 pub struct GameSession {
     pub board: Board,
-    pub current_player: Player,
+    pub current_player: Player, // 直接書き換えず set_current_player を使う（参加者か検証される）
     pub scores: HashMap<Player, PlayerScore>,
     pub round: usize,
     pub total_scores: HashMap<Player, i32>,
+    pub total_pieces: HashMap<Player, Vec<Piece>>, // 完了したラウンドで取得した駒
     pub game_mode: GameMode,
     pub players: Vec<Player>,
+    pub history: Vec<MoveRecord>,        // 現在のラウンドの手順
+    pub round_history: Vec<RoundRecord>, // 完了したラウンドの結果
+    pub tie_break: TieBreak,
+    pub sides_swapped: bool, // パイルールでFirstとSecondの移動方向が入れ替わったか
+    pub directions: HashMap<Player, MoveDirection>, // 各プレイヤーの移動方向（既定は Player::direction）
+    pub target_score: Option<i32>, // この合計得点に達したプレイヤーが出た時点でゲーム終了
+    pub max_rounds: Option<usize>, // このラウンド数を終えた時点でゲーム終了
+    pub repetition_draw: bool, // trueなら同じ局面が3回現れた時点でラウンドを引き分けで終える（手を指す前に設定する）
+    pub track_pieces: bool,    // falseなら取得した駒を記録せず得点だけを保持する（省メモリ用）
+    generator: Rc<dyn BoardGenerator>, // 次のラウンドの盤面の生成方法
+    round_finished: bool,      // 現在のラウンドの結果を合計に記録済みか
+    position_counts: HashMap<(u64, Player), usize>, // 現在のラウンドで各局面（盤面と手番）が現れた回数
 }
 
 impl GameSession {
+    #[cfg(feature = "rand")]
     pub fn new(size: BoardSize, game_mode: GameMode) -> Self {
-        let mut scores = HashMap::new();
-        let mut total_scores = HashMap::new();
-
-        // ゲームモードに応じたプレイヤーリスト
-        let players = match game_mode {
-            GameMode::TwoPlayers => vec![Player::First, Player::Second],
-            GameMode::FourPlayers => {
-                vec![Player::First, Player::Second, Player::Third, Player::Fourth]
-            }
-        };
-
-        for player in &players {
-            scores.insert(*player, PlayerScore::new());
-            total_scores.insert(*player, 0);
-        }
+        Self::new_with_board(Board::new(size), game_mode)
+    }
 
-        Self {
-            board: Board::new(size),
-            current_player: Player::First,
-            scores,
-            round: 1,
-            total_scores,
-            game_mode,
-            players,
-        }
+    // 独自の生成方法で盤面を作るセッション（以降のラウンドも同じ生成方法を使う）
+    #[cfg(feature = "rand")]
+    pub fn new_with_generator(
+        size: BoardSize,
+        game_mode: GameMode,
+        generator: impl BoardGenerator + 'static,
+    ) -> Self {
+        let board = generator.generate(size, &mut rand::thread_rng());
+        let mut session = Self::new_with_board(board, game_mode);
+        session.generator = Rc::new(generator);
+        session
     }
 
     pub fn new_with_board(board: Board, game_mode: GameMode) -> Self {
         let mut scores = HashMap::new();
         let mut total_scores = HashMap::new();
+        let mut total_pieces = HashMap::new();
 
-        // ゲームモードに応じたプレイヤーリスト
-        let players = match game_mode {
-            GameMode::TwoPlayers => vec![Player::First, Player::Second],
-            GameMode::FourPlayers => {
-                vec![Player::First, Player::Second, Player::Third, Player::Fourth]
-            }
-        };
+        let players = players_for(game_mode);
+
+        let directions = players
+            .iter()
+            .map(|player| (*player, player.direction()))
+            .collect();
 
         for player in &players {
             scores.insert(*player, PlayerScore::new());
             total_scores.insert(*player, 0);
+            total_pieces.insert(*player, Vec::new());
         }
 
-        Self {
+        let mut session = Self {
             board,
             current_player: Player::First,
             scores,
             round: 1,
             total_scores,
+            total_pieces,
             game_mode,
             players,
+            history: Vec::new(),
+            round_history: Vec::new(),
+            tie_break: TieBreak::None,
+            sides_swapped: false,
+            directions,
+            target_score: None,
+            max_rounds: None,
+            repetition_draw: false,
+            track_pieces: true,
+            generator: Rc::new(DefaultGenerator),
+            round_finished: false,
+            position_counts: HashMap::new(),
+        };
+        session.record_position();
+        session
+    }
+
+    // 盤面・手番・得点を指定してセッションを作成（途中局面の再現用）
+    // scores のキーはゲームモードの参加者と一致している必要がある
+    pub fn with_state(
+        board: Board,
+        game_mode: GameMode,
+        current_player: Player,
+        scores: HashMap<Player, PlayerScore>,
+    ) -> Result<Self, StateError> {
+        let mut session = Self::new_with_board(board, game_mode);
+
+        let keys_match = scores.len() == session.players.len()
+            && session
+                .players
+                .iter()
+                .all(|player| scores.contains_key(player));
+        if !keys_match || !session.players.contains(&current_player) {
+            return Err(StateError::PlayerMismatch);
         }
+
+        session.current_player = current_player;
+        session.scores = scores;
+        session.position_counts.clear();
+        session.record_position();
+        Ok(session)
     }
 
     // プレイヤーの移動を処理
-    pub fn process_move(&mut self, target: (usize, usize)) -> Result<(), String> {
-        let result = self.board.make_move(self.current_player, target);
+    pub fn process_move(&mut self, target: (usize, usize)) -> Result<(), MoveError> {
+        // 棄権などで参加していないプレイヤーの手は受け付けない
+        if !self.players.contains(&self.current_player) {
+            return Err(MoveError::PlayerNotInGame(self.current_player));
+        }
+        if self.is_round_over() {
+            return Err(MoveError::RoundOver);
+        }
+
+        let from = self.board.cross_position;
+        let passed = self.board.passed_pieces(target);
+        let direction = self.direction_of(self.current_player);
+        let result = self.board.make_move_for_direction(direction, target);
 
         match result {
             Ok(piece) => {
-                if let Piece::Number(_) = piece {
-                    self.scores
-                        .get_mut(&self.current_player)
-                        .unwrap()
-                        .add_piece(piece);
+                // 通過した駒と移動先の駒をすべて得点に加える
+                let score = self.scores.get_mut(&self.current_player).unwrap();
+                for captured in passed.iter().map(|(_, p)| *p).chain([piece]) {
+                    if !captured.is_scoring() {
+                        continue;
+                    }
+                    if self.track_pieces {
+                        score.add_piece(captured);
+                    } else {
+                        score.add_value(captured);
+                    }
+                }
+
+                // 盤面を空にした手にはルールのボーナスを加える（RoundEndedより前に反映される）
+                if self.board.is_game_over() {
+                    score.total += self.board.rules.last_mover_bonus;
+                }
+
+                self.history.push(MoveRecord {
+                    player: self.current_player,
+                    from,
+                    target,
+                    piece,
+                    passed,
+                });
+
+                // 特殊マスを取った場合は同じプレイヤーがもう一度移動する
+                if piece != Piece::Bonus(BonusKind::ExtraTurn) {
+                    self.current_player = self.player_after(self.current_player);
+                }
+                self.record_position();
+
+                #[cfg(debug_assertions)]
+                if let Err(violation) = self.check_invariants() {
+                    panic!(
+                        "invariant violated after move to {:?}: {}",
+                        target, violation
+                    );
                 }
-                self.current_player = self.current_player.next_for_mode(self.game_mode);
                 Ok(())
             }
             Err(e) => Err(e),
         }
     }
 
+    // 状態の整合性を検証し、崩れていればその内容を返す
+    // デバッグビルドでは process_move の成功後に毎回呼ばれ、崩れていればパニックする
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let crosses = self
+            .board
+            .pieces
+            .iter()
+            .filter(|piece| **piece == Piece::Cross)
+            .count();
+        if crosses != 1 {
+            return Err(format!("expected exactly one cross, found {}", crosses));
+        }
+
+        let (row, col) = self.board.cross_position;
+        if self.board.get_piece(row, col) != Piece::Cross {
+            return Err(format!(
+                "cross_position {:?} does not hold the cross",
+                (row, col)
+            ));
+        }
+        if let Some(player) = self.players.iter().find(|p| !self.scores.contains_key(p)) {
+            return Err(format!("{:?} has no score", player));
+        }
+        if !self.players.contains(&self.current_player) {
+            return Err(format!(
+                "current player {:?} is not in the game",
+                self.current_player
+            ));
+        }
+
+        Ok(())
+    }
+
+    // 現在の局面から depth 手以内でラウンドが終わり、player が勝者になる手順の数
+    // 全員が全ての合法手を指すものとして数える（depth 手で終わらない手順は数えない）
+    // 手順の数は手数に対して指数的に増えるので、depth は残りの駒が少ない局面で10手程度までにする
+    pub fn count_winning_lines(&self, player: Player, depth: usize) -> usize {
+        if self.is_round_over() {
+            return usize::from(self.get_round_winner() == Some(player));
+        }
+        if depth == 0 {
+            return 0;
+        }
+
+        self.valid_moves(self.current_player)
+            .into_iter()
+            .map(|target| {
+                let mut next = self.clone();
+                match next.process_move(target) {
+                    Ok(()) => next.count_winning_lines(player, depth - 1),
+                    Err(_) => 0,
+                }
+            })
+            .sum()
+    }
+
+    // 複数の操作をまとめて実行し、途中でエラーになった場合はすべて元に戻す
+    pub fn transaction(
+        &mut self,
+        f: impl FnOnce(&mut GameSession) -> Result<(), MoveError>,
+    ) -> Result<(), MoveError> {
+        let snapshot = self.clone();
+        let result = f(self);
+        if result.is_err() {
+            *self = snapshot;
+        }
+        result
+    }
+
+    // プレイヤーの移動方向（directions の設定とサイド交換を考慮）
+    // サイドを交換した場合、FirstとSecondは互いの移動方向を使う
+    pub fn direction_of(&self, player: Player) -> MoveDirection {
+        let side = match (self.sides_swapped, player) {
+            (true, Player::First) => Player::Second,
+            (true, Player::Second) => Player::First,
+            _ => player,
+        };
+        self.directions
+            .get(&side)
+            .copied()
+            .unwrap_or_else(|| side.direction())
+    }
+
+    // プレイヤーの有効な移動先（サイド交換を考慮）
+    pub fn valid_moves(&self, player: Player) -> Vec<(usize, usize)> {
+        self.board
+            .get_valid_moves_for_direction(self.direction_of(player))
+    }
+
+    // 現在のプレイヤーの有効な移動先を表示用に番号付けした一覧
+    // (1始まりの番号, 移動先, 取得できる得点) を valid_moves と同じ順序で返す
+    pub fn move_menu(&self) -> Vec<(usize, (usize, usize), i32)> {
+        let player = self.current_player;
+        self.valid_moves(player)
+            .into_iter()
+            .enumerate()
+            .map(|(i, target)| (i + 1, target, self.score_delta(player, target).unwrap_or(0)))
+            .collect()
+    }
+
+    // 現在のプレイヤーがそのマスに移動できない理由を返す（移動できる場合はNone）
+    pub fn explain_illegal(&self, target: (usize, usize)) -> Option<MoveError> {
+        if !self.players.contains(&self.current_player) {
+            return Some(MoveError::PlayerNotInGame(self.current_player));
+        }
+        if self.is_round_over() {
+            return Some(MoveError::RoundOver);
+        }
+
+        self.board
+            .check_target_for_direction(self.direction_of(self.current_player), target)
+            .err()
+    }
+
+    // 手番のプレイヤーを変更（参加していないプレイヤーは拒否）
+    pub fn set_current_player(&mut self, player: Player) -> Result<(), StateError> {
+        if !self.players.contains(&player) {
+            return Err(StateError::NotInGame(player));
+        }
+        self.current_player = player;
+        Ok(())
+    }
+
+    // パイルール: 後手が最初の手番で移動する代わりにサイドを交換する
+    // 先手の取得した駒は後手のものになり、先手は縦方向のプレイヤーとして次の手を指す
+    pub fn swap_sides(&mut self) -> Result<(), StateError> {
+        let first_move_only = self.history.len() == 1 && self.history[0].player == Player::First;
+        if self.game_mode != GameMode::TwoPlayers
+            || self.sides_swapped
+            || self.current_player != Player::Second
+            || !first_move_only
+        {
+            return Err(StateError::SwapNotAllowed);
+        }
+
+        let first = self.scores.remove(&Player::First).unwrap();
+        let second = self.scores.remove(&Player::Second).unwrap();
+        self.scores.insert(Player::First, second);
+        self.scores.insert(Player::Second, first);
+
+        self.sides_swapped = true;
+        self.current_player = Player::First;
+        Ok(())
+    }
+
+    // 指定したプレイヤーの次に手番が来るプレイヤー（不参加のプレイヤーは飛ばす）
+    fn player_after(&self, player: Player) -> Player {
+        let mut next = player.next_for_mode(self.game_mode);
+        for _ in 0..4 {
+            if self.players.contains(&next) {
+                break;
+            }
+            next = next.next_for_mode(self.game_mode);
+        }
+        next
+    }
+
+    // 次の手番のプレイヤー（棄権したプレイヤーは飛ばす。特殊マスによる追加の手番は考慮しない）
+    pub fn next_player(&self) -> Player {
+        self.player_after(self.current_player)
+    }
+
+    // 現在のラウンドの手番の順序（ラウンドの先手から1周分）
+    pub fn turn_order(&self) -> Vec<Player> {
+        let starting = self
+            .history
+            .first()
+            .map_or(self.current_player, |record| record.player);
+
+        let mut order = vec![starting];
+        while order.len() < self.players.len() {
+            order.push(self.player_after(*order.last().unwrap()));
+        }
+        order
+    }
+
+    // プレイヤーを棄権させる（以降は手番が回ってこない）
+    pub fn forfeit(&mut self, player: Player) {
+        if !self.players.contains(&player) {
+            return;
+        }
+
+        let next = self.player_after(player);
+        self.players.retain(|p| *p != player);
+        if self.current_player == player {
+            self.current_player = next;
+        }
+    }
+
+    // 現在のラウンドの手を全て取り消し、ラウンド開始時の状態に戻す
+    // 合計得点とラウンド番号は変更しない
+    // 結果を合計に記録済みのラウンド（ゲーム終了後など）は取り消せないので何もしない
+    pub fn undo_round(&mut self) {
+        if self.round_finished {
+            return;
+        }
+        let first_player = match self.history.first() {
+            Some(record) => record.player,
+            None => return,
+        };
+
+        // 新しい手から順に盤面を戻す
+        for record in self.history.iter().rev() {
+            for ((row, col), piece) in &record.passed {
+                self.board.set_piece(*row, *col, *piece);
+            }
+            self.board
+                .set_piece(record.target.0, record.target.1, record.piece);
+            self.board
+                .set_piece(record.from.0, record.from.1, Piece::Cross);
+        }
+        self.history.clear();
+
+        for player in &self.players {
+            self.scores.insert(*player, PlayerScore::new());
+        }
+        self.sides_swapped = false;
+        self.current_player = first_player;
+        self.position_counts.clear();
+        self.record_position();
+    }
+
+    // 現在のラウンドで行われた手数
+    pub fn moves_this_round(&self) -> usize {
+        self.history.len()
+    }
+
     // ゲームが終了したか確認
+    // 数値の駒が残っていても、現在のプレイヤーが移動できなければ終了
+    // （負の駒を取れないルールでは負の駒だけが残ることがある）
     pub fn is_round_over(&self) -> bool {
         self.board.is_game_over()
+            || self.valid_moves(self.current_player).is_empty()
+            || self.ended_by_repetition()
+    }
+
+    // 現在のプレイヤーが target へ移動するとラウンドが終わるか（アニメーションの切り替え用）
+    // 最後の駒を取る手のほか、次の手番のプレイヤーが動けなくなる手も含む
+    pub fn would_end_round(&self, target: (usize, usize)) -> Result<bool, MoveError> {
+        if let Some(e) = self.explain_illegal(target) {
+            return Err(e);
+        }
+
+        let mut next = self.clone();
+        next.process_move(target)?;
+        Ok(next.is_round_over())
+    }
+
+    // 現在の局面（盤面と手番）がこのラウンドで3回以上現れたか
+    // 局面は repetition_draw が有効な間だけ数えるので、無効なら常にfalse
+    pub fn is_repetition(&self) -> bool {
+        let key = (self.board.zobrist_hash(), self.current_player);
+        self.position_counts
+            .get(&key)
+            .is_some_and(|count| *count >= 3)
+    }
+
+    // 同一局面の繰り返しによる引き分けでラウンドが終わったか
+    fn ended_by_repetition(&self) -> bool {
+        self.repetition_draw && self.is_repetition()
+    }
+
+    // 現在の局面を出現回数に加える
+    // 標準のルールでは毎手駒が減って同じ局面は現れないので、repetition_draw が有効な場合だけ数える
+    // （探索で大量に複製されるセッションの負担を避けるため）
+    fn record_position(&mut self) {
+        if !self.repetition_draw {
+            return;
+        }
+        let key = (self.board.zobrist_hash(), self.current_player);
+        *self.position_counts.entry(key).or_insert(0) += 1;
     }
 
     // 現在のラウンドの勝者を取得
     pub fn get_round_winner(&self) -> Option<Player> {
-        if !self.is_round_over() {
+        if !self.is_round_over() || self.ended_by_repetition() {
             return None;
         }
 
-        // 全プレイヤーの中で最高得点を見つける
-        let mut highest_score = i32::MIN;
-        let mut winner: Option<Player> = None;
-        let mut is_tie = false;
+        // 全プレイヤーの中で最高得点を見つける（同点なら引き分け）
+        self.current_leader()
+    }
 
-        for player in &self.players {
-            let score = self.scores.get(player).unwrap().total;
+    // 現時点で単独トップのプレイヤーを取得（ラウンド途中でも使用可、同点ならNone）
+    pub fn current_leader(&self) -> Option<Player> {
+        unique_leader(&self.players, |player| self.scores[&player].total)
+    }
 
-            match score.cmp(&highest_score) {
-                std::cmp::Ordering::Greater => {
-                    highest_score = score;
-                    winner = Some(*player);
-                    is_tie = false;
-                }
-                std::cmp::Ordering::Equal => {
-                    is_tie = true;
-                }
-                std::cmp::Ordering::Less => {}
-            }
+    // 現在のラウンドの順位表（得点の高い順、同点はプレイヤー順）
+    pub fn round_standings(&self) -> Vec<(Player, i32)> {
+        let mut standings: Vec<(Player, i32)> = self
+            .players
+            .iter()
+            .map(|player| (*player, self.scores[player].total))
+            .collect();
+        standings.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+        standings
+    }
+
+    // 2人モードで現在のラウンドをリードしているプレイヤーと点差
+    // 同点の場合と4人モードではNone
+    pub fn two_player_lead(&self) -> Option<(Player, i32)> {
+        if self.game_mode != GameMode::TwoPlayers {
+            return None;
         }
 
-        if is_tie {
-            None // 引き分け
+        match self.round_standings().as_slice() {
+            [(leader, first), (_, second)] if first > second => Some((*leader, first - second)),
+            _ => None,
+        }
+    }
+
+    // このラウンドで到達しうる得点の上限（残りの正の駒を全て取り、負の駒を取らない場合）
+    // ラウンドが続いていれば、正の Rules::last_mover_bonus も得られるものとして加える
+    pub fn max_reachable_score(&self, player: Player) -> i32 {
+        let current = self.scores.get(&player).map_or(0, |score| score.total);
+        let bonus = if self.is_round_over() {
+            0
+        } else {
+            self.board.rules.last_mover_bonus.max(0)
+        };
+        current + self.board.remaining_positive() + bonus
+    }
+
+    // 現在のラウンドの勝者が事実上決まっているか（投了の提案用）
+    // 首位のプレイヤーが残りの負の駒を全て取っても、他の全員の max_reachable_score を下回らない場合
+    pub fn is_decided(&self) -> bool {
+        let standings = self.round_standings();
+        let Some(&(leader, score)) = standings.first() else {
+            return false;
+        };
+
+        // 負の駒を取れないルールなら首位の得点は下がらない
+        let penalties: i32 = if self.board.rules.allow_negative_capture {
+            self.board
+                .pieces
+                .iter()
+                .filter_map(|piece| piece.value())
+                .filter(|value| *value < 0)
+                .sum()
         } else {
-            winner
+            0
+        };
+        // 負のボーナスなら首位が最後の駒を取らされる場合も考える
+        let bonus = if self.is_round_over() {
+            0
+        } else {
+            self.board.rules.last_mover_bonus.min(0)
+        };
+        let floor = score + penalties + bonus;
+
+        standings
+            .iter()
+            .filter(|(player, _)| *player != leader)
+            .all(|(player, _)| self.max_reachable_score(*player) < floor)
+    }
+
+    // 盤面に残っている取得可能な駒の数（ラウンドの残り手数の上限）
+    // ラウンドが終了している（誰も移動できない）場合は0
+    pub fn pieces_remaining(&self) -> usize {
+        if self.is_round_over() {
+            return 0;
         }
+        self.board.occupancy().count_ones() as usize
+    }
+
+    // 現在のラウンドで全プレイヤーが取得した得点の合計（残りは board.remaining_positive()）
+    pub fn claimed_points(&self) -> i32 {
+        self.scores.values().map(|score| score.total).sum()
+    }
+
+    // 指定プレイヤーが指定マスを取った場合のラウンド得点の変化量
+    pub fn score_delta(&self, player: Player, target: (usize, usize)) -> Result<i32, MoveError> {
+        self.board
+            .check_target_for_direction(self.direction_of(player), target)?;
+
+        let passed = self.board.passed_pieces(target);
+        let passed_value: i32 = passed.iter().filter_map(|(_, piece)| piece.value()).sum();
+        let target_value = self
+            .board
+            .get_piece(target.0, target.1)
+            .value()
+            .unwrap_or(0);
+
+        // 残りの駒を全て取って盤面を空にする手には Rules::last_mover_bonus が加わる
+        let clears_board = self.board.occupancy().count_ones() as usize == passed.len() + 1;
+        let bonus = if clears_board {
+            self.board.rules.last_mover_bonus
+        } else {
+            0
+        };
+        Ok(passed_value + target_value + bonus)
     }
 
     // 次のラウンドを開始
     pub fn start_next_round(&mut self) {
-        // 現在のラウンドのスコアを合計に追加
-        for player in &self.players {
-            let round_score = self.scores.get(player).unwrap().total;
-            *self.total_scores.get_mut(player).unwrap() += round_score;
-        }
+        // 新しいラウンドの盤面を生成（ルールは引き継ぐ）
+        let rules = self.board.rules;
+        #[cfg(feature = "rand")]
+        let mut rng = rand::thread_rng();
+        // rand フィーチャーが無効な場合は前のラウンドの盤面とラウンド数から決まるシードを使う
+        #[cfg(not(feature = "rand"))]
+        let mut rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(
+            self.board.zobrist_hash() ^ self.round as u64,
+        );
+        let mut board = self.generator.generate(self.board.size, &mut rng);
+        board.rules = rules;
+        self.start_next_round_with_board(board);
+    }
 
-        // 新しいラウンドを初期化
-        self.board = Board::new(self.board.size);
+    // 指定した盤面で次のラウンドを開始（イベントログからの再構築用）
+    pub fn start_next_round_with_board(&mut self, board: Board) {
+        self.finish_round();
 
-        // スコアを初期化
-        for player in &self.players {
-            self.scores.insert(*player, PlayerScore::new());
-        }
+        self.board = board;
+        self.history.clear();
+        self.round_finished = false;
 
         // ラウンドをインクリメント
         self.round += 1;
@@ -177,36 +757,267 @@ impl GameSession {
             let index = (self.round - 1) % self.players.len();
             self.current_player = self.players[index];
         }
+
+        self.position_counts.clear();
+        self.record_position();
+    }
+
+    // 現在のラウンドの結果を記録し、ラウンドの得点を合計に移す（記録済みなら何もしない）
+    fn finish_round(&mut self) {
+        if self.round_finished {
+            return;
+        }
+        self.round_finished = true;
+
+        let reason = if self.board.is_game_over() {
+            RoundEndReason::BoardCleared
+        } else if self.ended_by_repetition() {
+            RoundEndReason::Repetition
+        } else if self.is_round_over() {
+            RoundEndReason::NoValidMoves
+        } else {
+            RoundEndReason::Abandoned
+        };
+        let winner = match reason {
+            RoundEndReason::Repetition => None,
+            _ => unique_leader(&self.players, |player| self.scores[&player].total),
+        };
+        self.round_history.push(RoundRecord {
+            winner,
+            scores: self.scores.clone(),
+            reason,
+        });
+
+        // 現在のラウンドのスコアを合計に追加
+        for player in &self.players {
+            let round_score = self.scores.get(player).unwrap();
+            *self.total_scores.get_mut(player).unwrap() += round_score.total;
+            self.total_pieces
+                .get_mut(player)
+                .unwrap()
+                .extend_from_slice(&round_score.pieces);
+        }
+
+        for player in &self.players {
+            self.scores.insert(*player, PlayerScore::new());
+        }
+    }
+
+    // 完了した各ラウンドの勝者（引き分けはNone）
+    pub fn round_winners_history(&self) -> Vec<Option<Player>> {
+        self.round_history
+            .iter()
+            .map(|record| record.winner)
+            .collect()
+    }
+
+    // ラウンドが終了している場合のみ次のラウンドを開始
+    pub fn try_start_next_round(&mut self) -> Result<(), StateError> {
+        if !self.is_round_over() {
+            return Err(StateError::RoundNotOver);
+        }
+
+        self.start_next_round();
+        Ok(())
+    }
+
+    // 合計得点が目標得点に達したプレイヤーがいるか
+    pub fn target_reached(&self) -> bool {
+        match self.target_score {
+            Some(target) => self.players.iter().any(|p| self.total_scores[p] >= target),
+            None => false,
+        }
+    }
+
+    // ラウンドが終了し、最大ラウンド数または目標得点の条件を満たしたか
+    // 目標得点は現在のラウンドの得点も含めて判定する（どちらも未設定なら常にfalse）
+    pub fn is_game_over(&self) -> bool {
+        if !self.is_round_over() {
+            return false;
+        }
+
+        let rounds_done = self.max_rounds.is_some_and(|max| self.round >= max);
+        let target_hit = self.target_score.is_some_and(|target| {
+            self.players
+                .iter()
+                .any(|p| self.total_scores[p] + self.scores[p].total >= target)
+        });
+        rounds_done || target_hit
+    }
+
+    // 完了した各ラウンドの後の player の合計得点の推移（グラフ表示用）
+    // 現在進行中のラウンドは含まない
+    pub fn cumulative_series(&self, player: Player) -> Vec<i32> {
+        self.round_history
+            .iter()
+            .scan(0, |total, record| {
+                *total += record.scores.get(&player).map_or(0, |score| score.total);
+                Some(*total)
+            })
+            .collect()
+    }
+
+    // 完了したラウンドを含むゲーム全体の結果
+    pub fn game_result(&self) -> GameResult {
+        let rounds = self
+            .round_history
+            .iter()
+            .map(|record| RoundResult {
+                winner: record.winner,
+                scores: record
+                    .scores
+                    .iter()
+                    .map(|(player, score)| (*player, score.total))
+                    .collect(),
+                reason: record.reason,
+            })
+            .collect();
+
+        GameResult {
+            overall_winner: self.get_overall_winner(),
+            total_scores: self.total_scores.clone(),
+            rounds,
+        }
     }
 
     // 総合勝者を取得
     pub fn get_overall_winner(&self) -> Option<Player> {
         // 全プレイヤーの中で最高の合計得点を見つける
-        let mut highest_total = i32::MIN;
-        let mut winner: Option<Player> = None;
-        let mut is_tie = false;
+        let highest_total = self.players.iter().map(|p| self.total_scores[p]).max()?;
+        let leaders: Vec<Player> = self
+            .players
+            .iter()
+            .copied()
+            .filter(|p| self.total_scores[p] == highest_total)
+            .collect();
 
-        for player in &self.players {
-            let total = *self.total_scores.get(player).unwrap();
+        if leaders.len() == 1 {
+            return Some(leaders[0]);
+        }
 
-            match total.cmp(&highest_total) {
-                std::cmp::Ordering::Greater => {
-                    highest_total = total;
-                    winner = Some(*player);
-                    is_tie = false;
-                }
-                std::cmp::Ordering::Equal => {
-                    is_tie = true;
-                }
-                std::cmp::Ordering::Less => {}
-            }
+        // 同点の場合はタイブレークのルールで決定
+        match self.tie_break {
+            TieBreak::None => None, // 引き分け
+            TieBreak::MostRoundsWon => unique_leader(&leaders, |player| {
+                self.round_history
+                    .iter()
+                    .filter(|record| record.winner == Some(player))
+                    .count() as i32
+            }),
+            // track_pieces が無効な場合は駒が記録されないので決着しない
+            TieBreak::FewestNegatives => unique_leader(&leaders, |player| {
+                let negatives = self
+                    .round_history
+                    .iter()
+                    .flat_map(|record| &record.scores[&player].pieces)
+                    .filter(|piece| piece.value().is_some_and(|n| n < 0))
+                    .count() as i32;
+                -negatives
+            }),
+            TieBreak::HeadToHead => unique_leader(&leaders, |player| {
+                // 同点のプレイヤー同士で単独最高得点だったラウンド数
+                self.round_history
+                    .iter()
+                    .filter(|record| {
+                        unique_leader(&leaders, |p| record.scores[&p].total) == Some(player)
+                    })
+                    .count() as i32
+            }),
         }
+    }
+
+    // 最善手を理由付きで取得
+    // 評価値は「取得値 - 移動後に次のプレイヤーが取れる最大値」
+    pub fn explain_best_move(&self, player: Player) -> Option<MoveExplanation> {
+        // 各候補手について取得値と次のプレイヤーの最大取得値を計算
+        // 取得値は score_delta と同じく通過した駒と Rules::last_mover_bonus を含む
+        let candidates: Vec<((usize, usize), i32, i32)> = self
+            .valid_moves(player)
+            .into_iter()
+            .filter_map(|target| {
+                let value = self.score_delta(player, target).ok()?;
+                let mut next = self.clone();
+                next.current_player = player;
+                next.process_move(target).ok()?;
+
+                // 特殊マスを取った場合は同じプレイヤーがもう一度移動する（棄権したプレイヤーは飛ばす）
+                let piece = next.history.last()?.piece;
+                let next_player = if piece == Piece::Bonus(BonusKind::ExtraTurn) {
+                    player
+                } else {
+                    self.player_after(player)
+                };
+                let reply = next
+                    .valid_moves(next_player)
+                    .into_iter()
+                    .map(|reply| next.score_delta(next_player, reply).unwrap_or(0))
+                    .max()
+                    .unwrap_or(0);
+                Some((target, value, reply))
+            })
+            .collect();
+
+        // 最も気前の良い候補手で次のプレイヤーが取れた値
+        let worst_reply = candidates.iter().map(|&(_, _, reply)| reply).max()?;
+
+        // 同点の場合は先に見つかった手を優先
+        candidates
+            .into_iter()
+            .rev()
+            .max_by_key(|&(_, value, reply)| value - reply)
+            .map(|(target, value, reply)| MoveExplanation {
+                target,
+                immediate_value: value,
+                denies_opponent: (worst_reply > reply).then_some(worst_reply - reply),
+            })
+    }
+
+    // 読み上げ用に盤面と現在のプレイヤーの選択肢を文章で説明（行・列は1始まり）
+    pub fn describe_options(&self) -> String {
+        let player = self.current_player;
+        let options: Vec<String> = self
+            .valid_moves(player)
+            .into_iter()
+            .map(|(row, col)| {
+                let value = match self.board.get_piece(row, col).value() {
+                    Some(n) => n.to_string(),
+                    None => "nothing".to_string(),
+                };
+                format!("row {}, column {} for {}", row + 1, col + 1, value)
+            })
+            .collect();
 
-        if is_tie {
-            None // 引き分け
+        if options.is_empty() {
+            format!("{} {:?} has no valid moves.", self.board.describe(), player)
         } else {
-            winner
+            format!(
+                "{} {:?} can move to: {}.",
+                self.board.describe(),
+                player,
+                options.join("; ")
+            )
+        }
+    }
+
+    // デバッグ用にセッションの状態をまとめて文字列化
+    pub fn debug_dump(&self) -> String {
+        let mut dump = String::new();
+        dump.push_str(&format!("Round: {}\n", self.round));
+        dump.push_str(&format!("Game mode: {:?}\n", self.game_mode));
+        dump.push_str(&format!("Current player: {:?}\n", self.current_player));
+        dump.push_str(&format!(
+            "Cross position: {:?}\n",
+            self.board.cross_position
+        ));
+        for player in &self.players {
+            dump.push_str(&format!(
+                "{:?}: round score {}, total {}\n",
+                player, self.scores[player].total, self.total_scores[player]
+            ));
         }
+        dump.push_str("Board:\n");
+        dump.push_str(&self.board.display());
+        dump
     }
 
     // 特定のプレイヤーの名前を取得
@@ -229,15 +1040,127 @@ impl GameSession {
     }
 }
 
+// 評価値が単独で最大のプレイヤーを取得（同点ならNone）
+fn unique_leader(players: &[Player], value: impl Fn(Player) -> i32) -> Option<Player> {
+    let mut highest = i32::MIN;
+    let mut leader: Option<Player> = None;
+    let mut is_tie = false;
+
+    for &player in players {
+        let score = value(player);
+
+        match score.cmp(&highest) {
+            std::cmp::Ordering::Greater => {
+                highest = score;
+                leader = Some(player);
+                is_tie = false;
+            }
+            std::cmp::Ordering::Equal => {
+                is_tie = true;
+            }
+            std::cmp::Ordering::Less => {}
+        }
+    }
+
+    if is_tie {
+        None
+    } else {
+        leader
+    }
+}
+
+// ゲームモードに応じたプレイヤーリスト
+fn players_for(game_mode: GameMode) -> Vec<Player> {
+    match game_mode {
+        GameMode::TwoPlayers => vec![Player::First, Player::Second],
+        GameMode::FourPlayers => {
+            vec![Player::First, Player::Second, Player::Third, Player::Fourth]
+        }
+    }
+}
+
 // ゲームイベントを表すenum
 #[derive(Debug, Clone)]
 pub enum GameEvent {
     GameStarted,
     RoundStarted(usize),
     MoveMade(Player, (usize, usize), Piece),
+    ScoreUpdated(Player, i32), // 得点になる駒を取った直後のラウンド得点
+    ExtraTurnGranted(Player),  // 特殊マスを取ってもう一度移動できる
     InvalidMove(Player, (usize, usize), String),
-    RoundEnded(Option<Player>, HashMap<Player, i32>),
-    GameEnded(Option<Player>, HashMap<Player, i32>),
+    SidesSwapped,
+    BoardGenerated(Board), // ラウンド開始時に生成された盤面のスナップショット
+    // 勝者、得点、取得した駒の一覧
+    RoundEnded(
+        Option<Player>,
+        HashMap<Player, i32>,
+        HashMap<Player, Vec<Piece>>,
+    ),
+    GameEnded(
+        Option<Player>,
+        HashMap<Player, i32>,
+        HashMap<Player, Vec<Piece>>,
+    ),
+}
+
+// JSON出力用のシリアライズ（各イベントを "event" タグ付きのオブジェクトにする）
+#[cfg(feature = "serde")]
+impl serde::Serialize for GameEvent {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        match self {
+            GameEvent::GameStarted => {
+                map.serialize_entry("event", "GameStarted")?;
+            }
+            GameEvent::RoundStarted(round) => {
+                map.serialize_entry("event", "RoundStarted")?;
+                map.serialize_entry("round", round)?;
+            }
+            GameEvent::MoveMade(player, target, piece) => {
+                map.serialize_entry("event", "MoveMade")?;
+                map.serialize_entry("player", player)?;
+                map.serialize_entry("target", target)?;
+                map.serialize_entry("piece", piece)?;
+            }
+            GameEvent::ScoreUpdated(player, score) => {
+                map.serialize_entry("event", "ScoreUpdated")?;
+                map.serialize_entry("player", player)?;
+                map.serialize_entry("score", score)?;
+            }
+            GameEvent::ExtraTurnGranted(player) => {
+                map.serialize_entry("event", "ExtraTurnGranted")?;
+                map.serialize_entry("player", player)?;
+            }
+            GameEvent::InvalidMove(player, target, reason) => {
+                map.serialize_entry("event", "InvalidMove")?;
+                map.serialize_entry("player", player)?;
+                map.serialize_entry("target", target)?;
+                map.serialize_entry("reason", reason)?;
+            }
+            GameEvent::SidesSwapped => {
+                map.serialize_entry("event", "SidesSwapped")?;
+            }
+            GameEvent::BoardGenerated(board) => {
+                map.serialize_entry("event", "BoardGenerated")?;
+                map.serialize_entry("board", &board.to_ascii())?;
+            }
+            GameEvent::RoundEnded(winner, scores, pieces) => {
+                map.serialize_entry("event", "RoundEnded")?;
+                map.serialize_entry("winner", winner)?;
+                map.serialize_entry("scores", scores)?;
+                map.serialize_entry("pieces", pieces)?;
+            }
+            GameEvent::GameEnded(winner, scores, pieces) => {
+                map.serialize_entry("event", "GameEnded")?;
+                map.serialize_entry("winner", winner)?;
+                map.serialize_entry("scores", scores)?;
+                map.serialize_entry("pieces", pieces)?;
+            }
+        }
+        map.end()
+    }
 }
 
 // ゲームイベントのリスナー
@@ -245,17 +1168,162 @@ pub trait GameEventListener {
     fn on_event(&mut self, event: GameEvent);
 }
 
+// イベントをチャネルに送信するリスナー（別スレッドでの描画用）
+pub struct ChannelListener {
+    sender: Sender<GameEvent>,
+}
+
+impl ChannelListener {
+    pub fn new(sender: Sender<GameEvent>) -> Self {
+        Self { sender }
+    }
+}
+
+impl GameEventListener for ChannelListener {
+    fn on_event(&mut self, event: GameEvent) {
+        // 受信側が破棄されている場合は捨てる
+        let _ = self.sender.send(event);
+    }
+}
+
+// 呼び出し側と共有するリスナー
+// GameManagerに所有権を渡した後も、呼び出し側から状態を参照できる
+pub struct SharedListener<T: GameEventListener> {
+    inner: Rc<RefCell<T>>,
+}
+
+impl<T: GameEventListener> SharedListener<T> {
+    pub fn new(inner: Rc<RefCell<T>>) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: GameEventListener> GameEventListener for SharedListener<T> {
+    fn on_event(&mut self, event: GameEvent) {
+        self.inner.borrow_mut().on_event(event);
+    }
+}
+
+// ゲームの統計情報
+#[derive(Debug, Clone, Default)]
+pub struct GameStats {
+    pub round_lengths: Vec<usize>, // 終了した各ラウンドの手数
+    pub moves_total: usize,        // ゲーム全体の手数
+    pub moves_by_player: HashMap<Player, usize>,
+    pub captured_by_player: HashMap<Player, i32>, // 取得した駒の値の合計
+    current_round_moves: usize,
+    round_scores: HashMap<Player, i32>, // 直前に通知されたラウンド得点
+}
+
+impl GameStats {
+    // プレイヤーの1手あたりの平均取得値（手数が0なら0.0）
+    pub fn average_capture_per_move(&self, player: Player) -> f32 {
+        let moves = self.moves_by_player.get(&player).copied().unwrap_or(0);
+        if moves == 0 {
+            return 0.0;
+        }
+
+        let captured = self.captured_by_player.get(&player).copied().unwrap_or(0);
+        captured as f32 / moves as f32
+    }
+}
+
+// イベントから統計情報を集計するリスナー
+#[derive(Debug, Default)]
+pub struct StatsListener {
+    pub stats: GameStats,
+}
+
+impl StatsListener {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl GameEventListener for StatsListener {
+    fn on_event(&mut self, event: GameEvent) {
+        match event {
+            GameEvent::RoundStarted(_) => {
+                self.stats.current_round_moves = 0;
+                self.stats.round_scores.clear();
+            }
+            GameEvent::MoveMade(player, _, _) => {
+                self.stats.current_round_moves += 1;
+                self.stats.moves_total += 1;
+                *self.stats.moves_by_player.entry(player).or_insert(0) += 1;
+            }
+            // 通過して取得した駒やボーナスも含めるため、得点の増分で集計する
+            GameEvent::ScoreUpdated(player, score) => {
+                let previous = self.stats.round_scores.insert(player, score).unwrap_or(0);
+                *self.stats.captured_by_player.entry(player).or_insert(0) += score - previous;
+            }
+            GameEvent::RoundEnded(..) => {
+                let moves = self.stats.current_round_moves;
+                self.stats.round_lengths.push(moves);
+            }
+            _ => {}
+        }
+    }
+}
+
+// ゲーム状態の読み取り専用ビュー
+// フロントエンドはセッションの内部構造に依存せずにこちらを使う
+pub struct GameView<'a> {
+    session: &'a GameSession,
+}
+
+impl<'a> GameView<'a> {
+    pub fn board(&self) -> &'a Board {
+        &self.session.board
+    }
+
+    pub fn current_player(&self) -> Player {
+        self.session.current_player
+    }
+
+    pub fn round(&self) -> usize {
+        self.session.round
+    }
+
+    pub fn players(&self) -> &'a [Player] {
+        &self.session.players
+    }
+
+    // 現在のラウンドの得点（ゲームに参加していないプレイヤーは0）
+    pub fn score(&self, player: Player) -> i32 {
+        self.session
+            .scores
+            .get(&player)
+            .map_or(0, |score| score.total)
+    }
+
+    // 完了したラウンドの合計得点
+    pub fn total_score(&self, player: Player) -> i32 {
+        self.session.total_scores.get(&player).copied().unwrap_or(0)
+    }
+
+    // 現在のプレイヤーの有効な移動先
+    pub fn valid_moves(&self) -> Vec<(usize, usize)> {
+        self.session.valid_moves(self.session.current_player)
+    }
+}
+
 // ゲームイベントを通知するゲームマネージャー
 pub struct GameManager {
     pub session: GameSession,
     listeners: Vec<Box<dyn GameEventListener>>,
+    started: bool,
+    round_ended_notified: bool, // 現在のラウンドでRoundEndedを通知済みか
 }
 
 impl GameManager {
+    #[cfg(feature = "rand")]
     pub fn new(size: BoardSize, game_mode: GameMode) -> Self {
         Self {
             session: GameSession::new(size, game_mode),
             listeners: Vec::new(),
+            started: false,
+            round_ended_notified: false,
         }
     }
 
@@ -263,37 +1331,199 @@ impl GameManager {
         Self {
             session: GameSession::new_with_board(board, game_mode),
             listeners: Vec::new(),
+            started: false,
+            round_ended_notified: false,
+        }
+    }
+
+    // 初期状態とイベントログからゲームの状態を再構築
+    // 各ラウンドの盤面は RoundStarted の直前の BoardGenerated から復元する
+    // 適用できないイベントがあれば、その番号とともにエラーを返す
+    pub fn apply_event_log(
+        initial: GameSession,
+        events: &[GameEvent],
+    ) -> Result<GameManager, EventLogError> {
+        let mut manager = GameManager {
+            session: initial,
+            listeners: Vec::new(),
+            started: false,
+            round_ended_notified: false,
+        };
+
+        // BoardGenerated で受け取り、続く RoundStarted で使う盤面
+        let mut pending_board: Option<Board> = None;
+
+        for (index, event) in events.iter().enumerate() {
+            match event {
+                GameEvent::GameStarted => manager.started = true,
+                GameEvent::BoardGenerated(board) => pending_board = Some(board.clone()),
+                GameEvent::RoundStarted(round) if *round > manager.session.round => {
+                    let board = pending_board
+                        .take()
+                        .ok_or(EventLogError::MissingBoard(index))?;
+                    manager.session.start_next_round_with_board(board);
+                }
+                GameEvent::RoundStarted(_) => {
+                    // 最初のラウンドの盤面も記録されていればそれを使う
+                    if let Some(board) = pending_board.take() {
+                        manager.session.board = board;
+                    }
+                }
+                // end_game と同じく終了済みのラウンドを合計に含める
+                GameEvent::GameEnded(..) if manager.session.is_round_over() => {
+                    manager.session.finish_round();
+                }
+                GameEvent::SidesSwapped => {
+                    manager
+                        .session
+                        .swap_sides()
+                        .map_err(|e| EventLogError::State(index, e))?;
+                }
+                GameEvent::MoveMade(player, target, _) => {
+                    manager
+                        .session
+                        .set_current_player(*player)
+                        .map_err(|e| EventLogError::State(index, e))?;
+                    manager
+                        .session
+                        .process_move(*target)
+                        .map_err(|e| EventLogError::IllegalMove(index, e))?;
+                }
+                _ => {}
+            }
         }
+
+        Ok(manager)
     }
 
     pub fn add_listener(&mut self, listener: Box<dyn GameEventListener>) {
         self.listeners.push(listener);
     }
 
+    // 呼び出し側と共有したままリスナーを登録
+    // イベント通知中に同じリスナーを借用するとパニックするので注意
+    pub fn add_shared_listener<T: GameEventListener + 'static>(
+        &mut self,
+        listener: Rc<RefCell<T>>,
+    ) {
+        self.add_listener(Box::new(SharedListener::new(listener)));
+    }
+
+    // パイルールによるサイド交換
+    pub fn swap_sides(&mut self) -> Result<(), StateError> {
+        self.session.swap_sides()?;
+        self.notify(GameEvent::SidesSwapped);
+        Ok(())
+    }
+
+    // 現在のラウンドをやり直す（手がないか、結果を記録済みなら何もしない）
+    pub fn undo_round(&mut self) {
+        if self.session.round_finished {
+            return;
+        }
+        self.session.undo_round();
+        self.round_ended_notified = false;
+    }
+
+    // 次の手番のプレイヤー
+    pub fn next_player(&self) -> Player {
+        self.session.next_player()
+    }
+
+    // 現在の状態の読み取り専用ビュー
+    pub fn current_state(&self) -> GameView<'_> {
+        GameView {
+            session: &self.session,
+        }
+    }
+
+    // イベントを受け取るチャネルを作成してリスナーとして登録
+    pub fn event_channel(&mut self) -> Receiver<GameEvent> {
+        let (sender, receiver) = mpsc::channel();
+        self.add_listener(Box::new(ChannelListener::new(sender)));
+        receiver
+    }
+
     fn notify(&mut self, event: GameEvent) {
         for listener in &mut self.listeners {
             listener.on_event(event.clone());
         }
     }
 
+    // ゲームモードを変更（ゲーム開始前のみ）
+    pub fn set_game_mode(&mut self, game_mode: GameMode) -> Result<(), StateError> {
+        if self.started || self.session.round > 1 || !self.session.history.is_empty() {
+            return Err(StateError::GameInProgress);
+        }
+
+        // プレイヤーとスコアだけをモードに合わせて作り直す（目標得点などの設定は残す）
+        let session = &mut self.session;
+        session.game_mode = game_mode;
+        session.players = players_for(game_mode);
+        session.current_player = Player::First;
+        session.scores.clear();
+        session.total_scores.clear();
+        session.total_pieces.clear();
+        for player in &session.players {
+            session.scores.insert(*player, PlayerScore::new());
+            session.total_scores.insert(*player, 0);
+            session.total_pieces.insert(*player, Vec::new());
+        }
+
+        // 参加しなくなったプレイヤーの移動方向は消し、新しいプレイヤーには既定の方向を使う
+        let players = session.players.clone();
+        session
+            .directions
+            .retain(|player, _| players.contains(player));
+        for player in players {
+            session
+                .directions
+                .entry(player)
+                .or_insert(player.direction());
+        }
+        Ok(())
+    }
+
     pub fn start_game(&mut self) {
+        self.started = true;
         self.notify(GameEvent::GameStarted);
+        self.notify(GameEvent::BoardGenerated(self.session.board.clone()));
         self.notify(GameEvent::RoundStarted(self.session.round));
     }
 
     pub fn make_move(&mut self, target: (usize, usize)) {
         let current_player = self.session.current_player;
+        let total_before = self
+            .session
+            .scores
+            .get(&current_player)
+            .map_or(0, |score| score.total);
 
         match self.session.process_move(target) {
             Ok(()) => {
-                // 移動した駒を取得
-                let pieces = &self.session.scores.get(&current_player).unwrap().pieces;
-                let last_piece = pieces.last().unwrap_or(&Piece::Empty);
+                // 移動した駒と通過して取得した駒を取得
+                let (piece, passed_scoring) =
+                    self.session
+                        .history
+                        .last()
+                        .map_or((Piece::Empty, false), |record| {
+                            let passed_scoring = record.passed.iter().any(|(_, p)| p.is_scoring());
+                            (record.piece, passed_scoring)
+                        });
 
-                self.notify(GameEvent::MoveMade(current_player, target, *last_piece));
+                self.notify(GameEvent::MoveMade(current_player, target, piece));
+                // Rules::last_mover_bonus だけで得点が変わった場合も通知する
+                let score = self.session.scores[&current_player].total;
+                if piece.is_scoring() || passed_scoring || score != total_before {
+                    self.notify(GameEvent::ScoreUpdated(current_player, score));
+                }
+                if piece == Piece::Bonus(BonusKind::ExtraTurn) {
+                    self.notify(GameEvent::ExtraTurnGranted(current_player));
+                }
 
-                // ラウンド終了チェック
-                if self.session.is_round_over() {
+                // ラウンド終了チェック（RoundEndedは1ラウンドに1回だけ通知する）
+                if self.session.is_round_over() && !self.round_ended_notified {
+                    self.round_ended_notified = true;
                     let winner = self.session.get_round_winner();
                     let scores = self
                         .session
@@ -301,26 +1531,64 @@ impl GameManager {
                         .iter()
                         .map(|(k, v)| (*k, v.total))
                         .collect();
+                    let pieces = self
+                        .session
+                        .scores
+                        .iter()
+                        .map(|(k, v)| (*k, v.pieces.clone()))
+                        .collect();
 
-                    self.notify(GameEvent::RoundEnded(winner, scores));
+                    self.notify(GameEvent::RoundEnded(winner, scores, pieces));
+
+                    // ゲーム終了条件を満たしていれば最終ラウンドの得点を合計に加えて終了
+                    if self.session.is_game_over() {
+                        self.end_game();
+                    }
                 }
             }
             Err(e) => {
-                self.notify(GameEvent::InvalidMove(current_player, target, e));
+                self.notify(GameEvent::InvalidMove(
+                    current_player,
+                    target,
+                    e.to_string(),
+                ));
             }
         }
     }
 
     pub fn start_next_round(&mut self) {
+        // 終了したゲームは次のラウンドに進まない
+        if self.session.is_game_over() {
+            return;
+        }
+
+        // 最後まで指したラウンドで目標得点に達した場合は is_game_over に含まれるが、
+        // 途中で打ち切ったラウンドはここで判定し、次の盤面を生成せずにゲームを終了する
+        self.session.finish_round();
+        if self.session.target_reached() {
+            self.end_game();
+            return;
+        }
+
         self.session.start_next_round();
+        self.round_ended_notified = false;
+
+        self.notify(GameEvent::BoardGenerated(self.session.board.clone()));
         self.notify(GameEvent::RoundStarted(self.session.round));
     }
 
-    pub fn end_game(&mut self) {
-        let winner = self.session.get_overall_winner();
+    // ゲームを終了して結果を返す（終了済みのラウンドは合計に含める）
+    pub fn end_game(&mut self) -> GameResult {
+        if self.session.is_round_over() {
+            self.session.finish_round();
+        }
+
+        let result = self.session.game_result();
         self.notify(GameEvent::GameEnded(
-            winner,
+            result.overall_winner,
             self.session.total_scores.clone(),
+            self.session.total_pieces.clone(),
         ));
+        result
     }
 }
@@ -1,5 +1,9 @@
 // src/lib.rs - ライブラリのエントリポイント
 
+pub mod ai;
 pub mod core;
 pub mod game;
+#[cfg(feature = "image")]
+pub mod render;
+pub mod replay;
 pub mod ui;
@@ -0,0 +1,139 @@
+// src/render.rs - 盤面の画像出力（ggezを使わずにサムネイルを作る）
+use crate::core::{Board, Piece};
+use image::{ImageOutputFormat, Rgba, RgbaImage};
+use std::io::Cursor;
+
+const BACKGROUND: Rgba<u8> = Rgba([230, 230, 230, 255]); // 駒のあるマス
+const EMPTY: Rgba<u8> = Rgba([190, 190, 190, 255]); // 空きマス
+const GRID: Rgba<u8> = Rgba([0, 0, 0, 255]); // セルの枠線
+const POSITIVE: Rgba<u8> = Rgba([0, 0, 0, 255]); // 正の数の駒
+const NEGATIVE: Rgba<u8> = Rgba([220, 0, 0, 255]); // 負の数の駒
+const CROSS: Rgba<u8> = Rgba([30, 80, 200, 255]); // クロスチップ
+const CROSS_MARK: Rgba<u8> = Rgba([255, 255, 255, 255]); // クロスチップの×印
+const BONUS: Rgba<u8> = Rgba([0, 150, 60, 255]); // 特殊マス
+
+// 3x5ドットの文字（各行の下位3ビットが左から右の点）
+fn glyph(c: char) -> Option<[u8; 5]> {
+    Some(match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b010, 0b010, 0b010],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        _ => return None,
+    })
+}
+
+// 盤面をPNGに描画（1マス cell_px ピクセル四方）
+pub fn render_png(board: &Board, cell_px: u32) -> Vec<u8> {
+    let (rows, cols) = board.size.dimensions();
+    let mut image = RgbaImage::from_pixel(cols as u32 * cell_px, rows as u32 * cell_px, BACKGROUND);
+
+    for row in 0..rows {
+        for col in 0..cols {
+            let x = col as u32 * cell_px;
+            let y = row as u32 * cell_px;
+
+            match board.get_piece(row, col) {
+                Piece::Number(n) => {
+                    let color = if n < 0 { NEGATIVE } else { POSITIVE };
+                    draw_text(&mut image, x, y, cell_px, &n.to_string(), color);
+                }
+                Piece::Cross => {
+                    fill_rect(&mut image, x, y, cell_px, cell_px, CROSS);
+                    draw_x(&mut image, x, y, cell_px);
+                }
+                Piece::Bonus(_) => draw_text(&mut image, x, y, cell_px, "+", BONUS),
+                Piece::Empty => fill_rect(&mut image, x, y, cell_px, cell_px, EMPTY),
+            }
+
+            stroke_rect(&mut image, x, y, cell_px);
+        }
+    }
+
+    let mut bytes = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut bytes), ImageOutputFormat::Png)
+        .expect("encoding a PNG into memory should not fail");
+    bytes
+}
+
+// 画像の外にはみ出す点は無視する
+fn put(image: &mut RgbaImage, x: u32, y: u32, color: Rgba<u8>) {
+    if x < image.width() && y < image.height() {
+        image.put_pixel(x, y, color);
+    }
+}
+
+fn fill_rect(image: &mut RgbaImage, x: u32, y: u32, width: u32, height: u32, color: Rgba<u8>) {
+    for dy in 0..height {
+        for dx in 0..width {
+            put(image, x + dx, y + dy, color);
+        }
+    }
+}
+
+fn stroke_rect(image: &mut RgbaImage, x: u32, y: u32, size: u32) {
+    for i in 0..size {
+        put(image, x + i, y, GRID);
+        put(image, x + i, y + size - 1, GRID);
+        put(image, x, y + i, GRID);
+        put(image, x + size - 1, y + i, GRID);
+    }
+}
+
+// クロスチップの×印
+fn draw_x(image: &mut RgbaImage, x: u32, y: u32, size: u32) {
+    let inset = size / 4;
+    for i in inset..size.saturating_sub(inset) {
+        put(image, x + i, y + i, CROSS_MARK);
+        put(image, x + size - 1 - i, y + i, CROSS_MARK);
+    }
+}
+
+// セルの中央に文字列を描画（1ドットはセルの1/16）
+fn draw_text(image: &mut RgbaImage, x: u32, y: u32, cell_px: u32, text: &str, color: Rgba<u8>) {
+    let dot = (cell_px / 16).max(1);
+    let width = (text.chars().count() as u32 * 4).saturating_sub(1) * dot;
+    let left = x + cell_px.saturating_sub(width) / 2;
+    let top = y + cell_px.saturating_sub(5 * dot) / 2;
+
+    for (i, c) in text.chars().enumerate() {
+        let Some(rows) = glyph(c) else {
+            continue;
+        };
+        let glyph_left = left + i as u32 * 4 * dot;
+        for (gy, bits) in rows.iter().enumerate() {
+            for gx in 0..3 {
+                if bits & (0b100 >> gx) != 0 {
+                    let px = glyph_left + gx * dot;
+                    let py = top + gy as u32 * dot;
+                    fill_rect(image, px, py, dot, dot, color);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::BoardSize;
+
+    #[test]
+    fn test_render_png() {
+        let board = Board::new_seeded(BoardSize::Large, 1);
+        let bytes = render_png(&board, 32);
+        assert!(bytes.starts_with(&[0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a]));
+
+        let decoded = image::load_from_memory(&bytes).unwrap();
+        assert_eq!((decoded.width(), decoded.height()), (6 * 32, 6 * 32));
+    }
+}
@@ -0,0 +1,206 @@
+// src/replay.rs - 対局の記録と再生
+use crate::core::{Board, BoardSize, GameMode, MoveError};
+use crate::game::GameSession;
+use std::collections::HashMap;
+use std::fmt;
+
+// リプレイに関するエラー
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayError {
+    InvalidNotation(String),       // 解釈できない手の表記
+    IllegalMove(usize, MoveError), // 再生中に不正だった手（手の番号は0始まり）
+    Incompatible,                  // シード・盤面サイズ・ゲームモードが異なるリプレイ同士
+    IndexOutOfRange(usize),        // 手の数を超える位置が指定された
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::InvalidNotation(token) => write!(f, "Invalid move notation '{}'", token),
+            ReplayError::IllegalMove(index, e) => write!(f, "Move {} is illegal: {}", index, e),
+            ReplayError::Incompatible => {
+                write!(f, "Replays do not share the same seed, board size and mode")
+            }
+            ReplayError::IndexOutOfRange(index) => {
+                write!(f, "Replay has no state after {} moves", index)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+// 初期盤面から手順を順に適用し、最初の不正な手の番号（0始まり）とエラーを返す
+pub fn validate_sequence(
+    initial: &Board,
+    mode: GameMode,
+    moves: &[(usize, usize)],
+) -> Result<(), (usize, MoveError)> {
+    let mut session = GameSession::new_with_board(initial.clone(), mode);
+    for (index, target) in moves.iter().enumerate() {
+        session.process_move(*target).map_err(|e| (index, e))?;
+    }
+    Ok(())
+}
+
+// 1ラウンド分の対局記録
+// シードから盤面を再生成し、手順を順に適用すれば同じ局面を再現できる
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Replay {
+    pub seed: u64,
+    pub size: BoardSize,
+    pub mode: GameMode,
+    pub moves: Vec<(usize, usize)>,
+    // 手の番号（0始まり）ごとの解説（注釈のない古い記録も読み込めるよう省略可）
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub annotations: HashMap<usize, String>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, size: BoardSize, mode: GameMode) -> Self {
+        Self {
+            seed,
+            size,
+            mode,
+            moves: Vec::new(),
+            annotations: HashMap::new(),
+        }
+    }
+
+    // シードから始めたセッションの現在のラウンドの手順を記録
+    pub fn record(seed: u64, session: &GameSession) -> Self {
+        Self {
+            seed,
+            size: session.board.size,
+            mode: session.game_mode,
+            moves: session.history.iter().map(|record| record.target).collect(),
+            annotations: HashMap::new(),
+        }
+    }
+
+    pub fn push(&mut self, target: (usize, usize)) {
+        self.moves.push(target);
+    }
+
+    // index 番目（0始まり）の手に解説を付ける（既にあれば置き換える）
+    pub fn annotate(&mut self, index: usize, text: &str) -> Result<(), ReplayError> {
+        if index >= self.moves.len() {
+            return Err(ReplayError::IndexOutOfRange(index));
+        }
+        self.annotations.insert(index, text.to_string());
+        Ok(())
+    }
+
+    // index 番目（0始まり）の手の解説
+    pub fn annotation(&self, index: usize) -> Option<&str> {
+        self.annotations.get(&index).map(String::as_str)
+    }
+
+    // 手を適用する前のセッション
+    pub fn initial_session(&self) -> GameSession {
+        GameSession::new_with_board(Board::new_seeded(self.size, self.seed), self.mode)
+    }
+
+    // 全ての手を適用したセッションを再現
+    pub fn play(&self) -> Result<GameSession, ReplayError> {
+        let mut session = self.initial_session();
+        for (index, target) in self.moves.iter().enumerate() {
+            session
+                .process_move(*target)
+                .map_err(|e| ReplayError::IllegalMove(index, e))?;
+        }
+        Ok(session)
+    }
+
+    // index 手を適用した直後のセッション（index 0 は初期状態）
+    // 途中の局面は保持せず、シードから index 手だけ再生する
+    pub fn state_at(&self, index: usize) -> Result<GameSession, ReplayError> {
+        if index > self.moves.len() {
+            return Err(ReplayError::IndexOutOfRange(index));
+        }
+
+        let mut session = self.initial_session();
+        for (i, target) in self.moves[..index].iter().enumerate() {
+            session
+                .process_move(*target)
+                .map_err(|e| ReplayError::IllegalMove(i, e))?;
+        }
+        Ok(session)
+    }
+
+    // 初期状態から1手ずつ適用したセッションを順に返す（不正な手の直前で終わる）
+    pub fn iter_states(&self) -> impl Iterator<Item = GameSession> + '_ {
+        let initial = self.initial_session();
+        let states = self.moves.iter().scan(initial.clone(), |session, target| {
+            session.process_move(*target).ok()?;
+            Some(session.clone())
+        });
+        std::iter::once(initial).chain(states)
+    }
+
+    // 全ての手が合法か検証（最初の不正な手の番号とエラーを返す）
+    pub fn validate(&self) -> Result<(), ReplayError> {
+        let board = Board::new_seeded(self.size, self.seed);
+        validate_sequence(&board, self.mode, &self.moves)
+            .map_err(|(index, e)| ReplayError::IllegalMove(index, e))
+    }
+
+    // 2つのリプレイで最初に手が異なる位置（0始まり）
+    // 片方が途中で終わっている場合は短い方の長さ、完全に一致すればNone
+    pub fn first_divergence(&self, other: &Replay) -> Result<Option<usize>, ReplayError> {
+        if self.seed != other.seed || self.size != other.size || self.mode != other.mode {
+            return Err(ReplayError::Incompatible);
+        }
+
+        let common = self
+            .moves
+            .iter()
+            .zip(&other.moves)
+            .position(|(a, b)| a != b);
+        Ok(match common {
+            Some(index) => Some(index),
+            None if self.moves.len() != other.moves.len() => {
+                Some(self.moves.len().min(other.moves.len()))
+            }
+            None => None,
+        })
+    }
+
+    // 手順を空白区切りの表記に変換（例: "r1c3 r4c3 r4c1"）
+    pub fn to_notation(&self) -> String {
+        let board = self.initial_session().board;
+        self.moves
+            .iter()
+            .map(|target| board.move_notation(*target))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    // 表記から手順を読み込む
+    pub fn from_notation(
+        seed: u64,
+        size: BoardSize,
+        mode: GameMode,
+        notation: &str,
+    ) -> Result<Self, ReplayError> {
+        let mut replay = Replay::new(seed, size, mode);
+        for token in notation.split_whitespace() {
+            let target = parse_notation(token)
+                .ok_or_else(|| ReplayError::InvalidNotation(token.to_string()))?;
+            replay.push(target);
+        }
+        Ok(replay)
+    }
+}
+
+// "r1c3" 形式の表記を0始まりの座標に変換
+fn parse_notation(token: &str) -> Option<(usize, usize)> {
+    let (row, col) = token.strip_prefix('r')?.split_once('c')?;
+    let row: usize = row.parse().ok()?;
+    let col: usize = col.parse().ok()?;
+    if row == 0 || col == 0 {
+        return None;
+    }
+    Some((row - 1, col - 1))
+}
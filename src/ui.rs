@@ -1,100 +1,205 @@
 // src/ui.rs - UI関連のコード
-use crate::core::{Board, BoardSize, GameMode};
-use crate::game::{GameEvent, GameEventListener, GameManager};
+use crate::core::{Board, BoardSize, GameMode, Piece};
+use crate::game::{GameEvent, GameEventListener, GameManager, GameSession};
+use crate::replay::Replay;
+use std::fmt;
 use std::io::{self, Write};
 
+// 移動入力のパースエラー
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseMoveError {
+    WrongFieldCount(usize), // カンマ区切りの要素数が2でない
+    Negative(String),       // 負の座標
+    NotANumber(String),     // 数値として解釈できない
+    NoSuchOption(usize),    // 移動メニューにない番号
+}
+
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMoveError::WrongFieldCount(count) => {
+                write!(f, "expected 'row,col' but got {} field(s)", count)
+            }
+            ParseMoveError::Negative(token) => {
+                write!(f, "coordinate '{}' must not be negative", token)
+            }
+            ParseMoveError::NotANumber(token) => write!(f, "'{}' is not a number", token),
+            ParseMoveError::NoSuchOption(index) => write!(f, "there is no move number {}", index),
+        }
+    }
+}
+
+impl std::error::Error for ParseMoveError {}
+
+// "row,col" 形式の入力を座標にパース
+pub fn parse_move(input: &str) -> Result<(usize, usize), ParseMoveError> {
+    let fields: Vec<&str> = input.trim().split(',').map(str::trim).collect();
+    if fields.len() != 2 {
+        return Err(ParseMoveError::WrongFieldCount(fields.len()));
+    }
+
+    let parse = |token: &str| match token.parse::<i64>() {
+        Ok(n) if n < 0 => Err(ParseMoveError::Negative(token.to_string())),
+        _ => token
+            .parse::<usize>()
+            .map_err(|_| ParseMoveError::NotANumber(token.to_string())),
+    };
+
+    Ok((parse(fields[0])?, parse(fields[1])?))
+}
+
+// 移動メニューの番号か "row,col" 形式の入力を座標にパース
+pub fn parse_move_choice(
+    input: &str,
+    menu: &[(usize, (usize, usize), i32)],
+) -> Result<(usize, usize), ParseMoveError> {
+    let input = input.trim();
+    if input.contains(',') {
+        return parse_move(input);
+    }
+
+    let index = input
+        .parse::<usize>()
+        .map_err(|_| ParseMoveError::NotANumber(input.to_string()))?;
+    menu.iter()
+        .find(|(number, _, _)| *number == index)
+        .map(|&(_, target, _)| target)
+        .ok_or(ParseMoveError::NoSuchOption(index))
+}
+
 // コンソールUI
 pub struct ConsoleUI {
     manager: GameManager,
+    sort_scores: bool, // スコアを得点の高い順に表示するか
+    events_json: bool, // イベントをJSON行として標準出力に書き出すか（対話用の表示は標準エラー）
 }
 
 impl ConsoleUI {
+    #[cfg(feature = "rand")]
     pub fn new(size: BoardSize, game_mode: GameMode) -> Self {
-        let manager = GameManager::new(size, game_mode);
+        Self::with_manager(GameManager::new(size, game_mode))
+    }
+
+    // シードを指定して最初の盤面を生成する（不具合の再現や盤面の共有用）
+    pub fn new_seeded(size: BoardSize, game_mode: GameMode, seed: u64) -> Self {
+        let board = Board::new_seeded(size, seed);
+        Self::with_manager(GameManager::new_with_board(board, game_mode))
+    }
+
+    fn with_manager(manager: GameManager) -> Self {
+        Self {
+            manager,
+            sort_scores: false,
+            events_json: false,
+        }
+    }
+
+    // 現在の盤面
+    pub fn board(&self) -> &Board {
+        &self.manager.session.board
+    }
 
-        // セルフを登録できないのでここではリスナーは登録しない
-        // ゲーム開始後に別途登録する
+    // スコアを得点の高い順に表示する
+    pub fn set_sort_scores(&mut self, sort_scores: bool) {
+        self.sort_scores = sort_scores;
+    }
+
+    // 人が読む形式のイベント表示の代わりに、イベントをJSON行として標準出力に書き出す
+    // 盤面やプロンプトなどの対話用の表示は標準エラーに出力される
+    #[cfg(feature = "serde")]
+    pub fn set_events_json(&mut self) {
+        self.events_json = true;
+        self.manager
+            .add_listener(Box::new(JsonEventListener::stdout()));
+    }
 
-        Self { manager }
+    // 追加のイベントリスナーを登録
+    pub fn add_listener(&mut self, listener: Box<dyn GameEventListener>) {
+        self.manager.add_listener(listener);
     }
 
     pub fn run(&mut self) {
+        // JSON出力時は標準出力をイベント専用にし、対話用の表示は標準エラーに出す
+        let mut out: Box<dyn Write> = if self.events_json {
+            Box::new(io::stderr())
+        } else {
+            // イベント表示はマネージャーが所有する別オブジェクトに任せる
+            self.manager.add_listener(Box::new(ConsoleEventPrinter));
+            Box::new(io::stdout())
+        };
+
         // ゲーム開始
         self.manager.start_game();
 
         loop {
             // 盤面表示
-            println!("{}", self.manager.session.board.display());
+            writeln!(out, "{}", self.manager.session.board.display()).unwrap();
 
             // 現在のプレイヤーとスコアを表示
             let current = self.manager.session.current_player;
-            println!("Current player: {:?}", current);
+            writeln!(out, "Current player: {:?}", current).unwrap();
 
             // すべてのプレイヤーのスコアを表示
-            for player in &self.manager.session.players {
-                let score = &self.manager.session.scores[player];
-                println!("{:?} score: {}", player, score.total);
+            if self.sort_scores {
+                write!(out, "{}", format_standings(&self.manager.session)).unwrap();
+            } else {
+                for player in &self.manager.session.players {
+                    let score = &self.manager.session.scores[player];
+                    writeln!(out, "{:?} score: {}", player, score.total).unwrap();
+                }
+            }
+
+            if self.manager.session.board.only_negatives_remain() {
+                writeln!(out, "Only penalties left!").unwrap();
             }
 
-            // 有効な移動を表示
-            let valid_moves = self.manager.session.board.get_valid_moves(current);
-            println!("Valid moves: {:?}", valid_moves);
+            // 有効な移動を番号付きで表示
+            let menu = self.manager.session.move_menu();
+            writeln!(out, "Valid moves:").unwrap();
+            write!(out, "{}", format_move_menu(&menu)).unwrap();
 
             // 入力受付
-            print!("Enter move (row,col): ");
-            io::stdout().flush().unwrap();
+            write!(out, "Enter move number or (row,col): ").unwrap();
+            out.flush().unwrap();
 
             let mut input = String::new();
             io::stdin().read_line(&mut input).unwrap();
 
             let input = input.trim();
             if input == "quit" {
+                self.manager.end_game();
                 break;
             }
 
             // 入力をパース
-            let coords: Vec<&str> = input.split(',').collect();
-            if coords.len() != 2 {
-                println!("Invalid input! Enter as 'row,col' (例: 0,1)");
-                println!("Valid moves are: {:?}", valid_moves);
-                continue;
-            }
-
-            let row = coords[0].trim().parse::<usize>();
-            let col = coords[1].trim().parse::<usize>();
-
-            if row.is_err() || col.is_err() {
-                println!("Invalid coordinates! Must be numbers (例: 0,1)");
-                println!("Valid moves are: {:?}", valid_moves);
-                continue;
-            }
-
-            let target = (row.unwrap(), col.unwrap());
+            let target = match parse_move_choice(input, &menu) {
+                Ok(target) => target,
+                Err(e) => {
+                    writeln!(out, "Invalid input: {} (例: 1 または 0,1)", e).unwrap();
+                    write!(out, "{}", format_move_menu(&menu)).unwrap();
+                    continue;
+                }
+            };
 
             // 有効な移動かチェック
-            if !valid_moves.contains(&target) {
-                println!(
-                    "Invalid move! The position ({},{}) is not a valid move.",
-                    target.0, target.1
-                );
-                println!("Valid moves are: {:?}", valid_moves);
+            if let Some(reason) = self.manager.session.explain_illegal(target) {
+                writeln!(out, "Invalid move! {}.", reason).unwrap();
+                write!(out, "{}", format_move_menu(&menu)).unwrap();
                 continue;
             }
 
             // 移動実行
             self.manager.make_move(target);
 
-            // ラウンド終了チェック
-            if self.manager.session.is_round_over() {
-                println!("Round {} ended!", self.manager.session.round);
-
-                match self.manager.session.get_round_winner() {
-                    Some(winner) => println!("Winner: {:?}", winner),
-                    None => println!("It's a draw!"),
-                }
+            // ゲーム終了チェック（結果はGameEndedイベントで表示される）
+            if self.manager.session.is_game_over() {
+                break;
+            }
 
-                print!("Start next round? (y/n): ");
-                io::stdout().flush().unwrap();
+            // ラウンド終了チェック（結果はRoundEndedイベントで表示される）
+            if self.manager.session.is_round_over() {
+                write!(out, "Start next round? (y/n): ").unwrap();
+                out.flush().unwrap();
 
                 let mut input = String::new();
                 io::stdin().read_line(&mut input).unwrap();
@@ -107,25 +212,20 @@ impl ConsoleUI {
                 }
             }
         }
+    }
+}
 
-        // ゲーム終了時の総合結果
-        println!("Game over!");
-        println!("Final scores:");
-        for player in &self.manager.session.players {
-            println!(
-                "{:?}: {}",
-                player, self.manager.session.total_scores[player]
-            );
-        }
+// コンソールにゲームイベントを表示するリスナー
+// ConsoleUI::run でマネージャーに登録され、ConsoleUI自身のイベントを表示する
+pub struct ConsoleEventPrinter;
 
-        match self.manager.session.get_overall_winner() {
-            Some(winner) => println!("Overall winner: {:?}", winner),
-            None => println!("Overall result: Draw"),
-        }
+impl GameEventListener for ConsoleUI {
+    fn on_event(&mut self, event: GameEvent) {
+        ConsoleEventPrinter.on_event(event);
     }
 }
 
-impl GameEventListener for ConsoleUI {
+impl GameEventListener for ConsoleEventPrinter {
     fn on_event(&mut self, event: GameEvent) {
         match event {
             GameEvent::GameStarted => {
@@ -137,20 +237,33 @@ impl GameEventListener for ConsoleUI {
             GameEvent::MoveMade(player, target, piece) => {
                 println!("{:?} moved to {:?} and got {:?}", player, target, piece);
             }
+            GameEvent::ScoreUpdated(player, score) => {
+                println!("{:?} now has {} points", player, score);
+            }
+            GameEvent::ExtraTurnGranted(player) => {
+                println!("{:?} gets an extra turn!", player);
+            }
             GameEvent::InvalidMove(player, target, reason) => {
                 println!("Invalid move by {:?} to {:?}: {}", player, target, reason);
             }
-            GameEvent::RoundEnded(winner, scores) => {
+            GameEvent::SidesSwapped => {
+                println!("Sides swapped!");
+            }
+            GameEvent::BoardGenerated(_) => {
+                // 盤面はラウンド開始後の表示で出力するので何もしない
+            }
+            GameEvent::RoundEnded(winner, scores, pieces) => {
                 println!("Round ended!");
                 for (player, score) in scores {
                     println!("{:?} score: {}", player, score);
+                    println!("{:?} captured: {}", player, format_pieces(&pieces[&player]));
                 }
                 match winner {
                     Some(w) => println!("Winner: {:?}", w),
                     None => println!("Round ended in a draw"),
                 }
             }
-            GameEvent::GameEnded(winner, scores) => {
+            GameEvent::GameEnded(winner, scores, _pieces) => {
                 println!("Game ended!");
                 for (player, score) in scores {
                     println!("{:?} total score: {}", player, score);
@@ -164,6 +277,106 @@ impl GameEventListener for ConsoleUI {
     }
 }
 
+// 各イベントをJSON形式で1行ずつ書き出すリスナー
+#[cfg(feature = "serde")]
+pub struct JsonEventListener<W: Write> {
+    writer: W,
+}
+
+#[cfg(feature = "serde")]
+impl<W: Write> JsonEventListener<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl JsonEventListener<io::Stdout> {
+    // 標準出力に書き出すリスナーを作成
+    pub fn stdout() -> Self {
+        Self::new(io::stdout())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<W: Write> GameEventListener for JsonEventListener<W> {
+    fn on_event(&mut self, event: GameEvent) {
+        // 書き込みに失敗してもゲームは継続する
+        if serde_json::to_writer(&mut self.writer, &event).is_ok() {
+            let _ = writeln!(self.writer);
+            let _ = self.writer.flush();
+        }
+    }
+}
+
+// 現在のラウンドのスコアを得点の高い順に1行ずつ整形
+pub fn format_standings(session: &GameSession) -> String {
+    session
+        .round_standings()
+        .iter()
+        .map(|(player, score)| format!("{:?} score: {}\n", player, score))
+        .collect()
+}
+
+// 移動メニューを "1) (1,3) +5" の形式で1行ずつ整形
+pub fn format_move_menu(menu: &[(usize, (usize, usize), i32)]) -> String {
+    menu.iter()
+        .map(|(number, (row, col), value)| format!("{}) ({},{}) {:+}\n", number, row, col, value))
+        .collect()
+}
+
+// リプレイを1手ずつ表示する（Enterで次の手、qで終了）
+pub fn view_replay(replay: &Replay) {
+    let mut states = replay.iter_states();
+    if let Some(initial) = states.next() {
+        println!("{}", initial.board.display());
+    }
+
+    for (index, session) in states.enumerate() {
+        print!("Press Enter for move {} (q to quit): ", index + 1);
+        io::stdout().flush().unwrap();
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).unwrap();
+        if input.trim() == "q" {
+            return;
+        }
+
+        print!("{}", format_replay_step(replay, index, &session));
+    }
+
+    if let Err(e) = replay.validate() {
+        println!("Replay stopped: {}", e);
+    }
+}
+
+// index 番目（0始まり）の手を適用した直後の盤面と解説を整形
+pub fn format_replay_step(replay: &Replay, index: usize, session: &GameSession) -> String {
+    let mut text = match session.history.last() {
+        Some(record) => format!(
+            "Move {}: {:?} to {:?}\n",
+            index + 1,
+            record.player,
+            record.target
+        ),
+        None => format!("Move {}\n", index + 1),
+    };
+    text.push_str(&session.board.display());
+    if let Some(note) = replay.annotation(index) {
+        text.push_str(&format!("Note: {}\n", note));
+    }
+    text
+}
+
+// 取得した駒を "5, 3, -2" の形式で表示
+fn format_pieces(pieces: &[Piece]) -> String {
+    pieces
+        .iter()
+        .map(|piece| piece.to_string().trim().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 // GUIのトレイトを定義（将来的な拡張用）
 pub trait GUI {
     fn init(&mut self);
@@ -172,3 +385,118 @@ pub trait GUI {
     fn show_message(&mut self, message: &str);
     fn close(&mut self);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_format_standings() {
+        use crate::core::Player;
+
+        let mut session = GameSession::new(BoardSize::Small, GameMode::FourPlayers);
+        session
+            .scores
+            .get_mut(&Player::Third)
+            .unwrap()
+            .add_piece(Piece::Number(7));
+        session
+            .scores
+            .get_mut(&Player::Second)
+            .unwrap()
+            .add_piece(Piece::Number(2));
+
+        let standings = format_standings(&session);
+        let lines: Vec<&str> = standings.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "Third score: 7");
+        assert_eq!(lines[1], "Second score: 2");
+        assert_eq!(lines[2], "First score: 0");
+    }
+
+    #[test]
+    fn test_new_seeded_is_deterministic() {
+        let ui = ConsoleUI::new_seeded(BoardSize::Large, GameMode::TwoPlayers, 42);
+        let again = ConsoleUI::new_seeded(BoardSize::Large, GameMode::TwoPlayers, 42);
+        assert_eq!(ui.board(), again.board());
+        assert_eq!(ui.board(), &Board::new_seeded(BoardSize::Large, 42));
+    }
+
+    #[test]
+    fn test_parse_move_choice() {
+        let menu = vec![(1, (0, 1), 5), (2, (0, 3), -2)];
+        assert_eq!(parse_move_choice("2", &menu), Ok((0, 3)));
+        assert_eq!(parse_move_choice(" 1 ", &menu), Ok((0, 1)));
+        assert_eq!(parse_move_choice("2,2", &menu), Ok((2, 2)));
+        assert_eq!(
+            parse_move_choice("3", &menu),
+            Err(ParseMoveError::NoSuchOption(3))
+        );
+        assert_eq!(
+            parse_move_choice("x", &menu),
+            Err(ParseMoveError::NotANumber("x".to_string()))
+        );
+        assert_eq!(format_move_menu(&menu), "1) (0,1) +5\n2) (0,3) -2\n");
+    }
+
+    #[test]
+    fn test_format_replay_step() {
+        let mut replay = Replay::new(3, BoardSize::Small, GameMode::TwoPlayers);
+        let target = replay
+            .initial_session()
+            .valid_moves(crate::core::Player::First)[0];
+        replay.push(target);
+        replay.annotate(0, "Take the biggest piece").unwrap();
+
+        let session = replay.state_at(1).unwrap();
+        let step = format_replay_step(&replay, 0, &session);
+        assert!(step.starts_with(&format!("Move 1: First to {:?}\n", target)));
+        assert!(step.ends_with("Note: Take the biggest piece\n"));
+    }
+
+    #[test]
+    fn test_parse_move_valid() {
+        assert_eq!(parse_move("1,2"), Ok((1, 2)));
+        assert_eq!(parse_move("1 , 2"), Ok((1, 2)));
+    }
+
+    #[test]
+    fn test_parse_move_wrong_field_count() {
+        assert_eq!(parse_move("1"), Err(ParseMoveError::WrongFieldCount(1)));
+    }
+
+    #[test]
+    fn test_parse_move_not_a_number() {
+        assert_eq!(
+            parse_move("a,b"),
+            Err(ParseMoveError::NotANumber("a".to_string()))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_event_listener_move_made() {
+        use crate::core::Player;
+
+        let mut listener = JsonEventListener::new(Vec::new());
+        listener.on_event(GameEvent::MoveMade(Player::First, (1, 2), Piece::Number(5)));
+
+        let output = String::from_utf8(listener.writer).unwrap();
+        assert!(output.ends_with('\n'));
+
+        let json: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+        assert_eq!(json["event"], "MoveMade");
+        assert_eq!(json["player"], "First");
+        assert_eq!(json["target"], serde_json::json!([1, 2]));
+        assert_eq!(json["piece"], serde_json::json!({ "Number": 5 }));
+    }
+
+    #[test]
+    fn test_parse_move_negative() {
+        assert_eq!(
+            parse_move("-1,0"),
+            Err(ParseMoveError::Negative("-1".to_string()))
+        );
+    }
+}
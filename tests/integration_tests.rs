@@ -1,8 +1,18 @@
 #[cfg(test)]
 mod integration_tests {
-    use micattix::core::{Board, BoardSize, GameMode, Piece, Player};
-    use micattix::game::{GameEvent, GameEventListener, GameManager};
-    use std::collections::VecDeque;
+    use micattix::ai;
+    use micattix::core::{
+        Board, BoardError, BoardGenerator, BoardSize, BonusKind, GameMode, GamePhase,
+        MoveDirection, MoveError, Movement, Piece, Player,
+    };
+    use micattix::game::{
+        EventLogError, GameEvent, GameEventListener, GameManager, GameSession, PlayerScore,
+        RoundEndReason, SharedListener, StateError, StatsListener, TieBreak,
+    };
+    use micattix::replay::{validate_sequence, Replay, ReplayError};
+    use std::cell::RefCell;
+    use std::collections::{HashMap, VecDeque};
+    use std::rc::Rc;
 
     // シンプルなイベントリスナー
     struct SimpleEventRecorder {
@@ -23,6 +33,40 @@ mod integration_tests {
         }
     }
 
+    // テスト側からイベントを参照できるレコーダー
+    struct SharedEventRecorder {
+        events: Rc<RefCell<Vec<GameEvent>>>,
+    }
+
+    impl GameEventListener for SharedEventRecorder {
+        fn on_event(&mut self, event: GameEvent) {
+            self.events.borrow_mut().push(event);
+        }
+    }
+
+    // クロスチップ以外が空の盤面を作成
+    fn empty_board_with_cross(cross: (usize, usize)) -> Board {
+        let mut board = Board::new(BoardSize::Small);
+        for row in 0..4 {
+            for col in 0..4 {
+                board.set_piece(row, col, Piece::Empty);
+            }
+        }
+        board.set_piece(cross.0, cross.1, Piece::Cross);
+        board
+    }
+
+    // 各プレイヤーが指定の駒を取ったものとしてラウンドを終える
+    fn finish_scripted_round(session: &mut GameSession, captures: &[(Player, &[i32])]) {
+        for (player, values) in captures {
+            let score = session.scores.get_mut(player).unwrap();
+            for value in values.iter() {
+                score.add_piece(Piece::Number(*value));
+            }
+        }
+        session.start_next_round();
+    }
+
     #[test]
     fn test_two_player_game_flow() {
         // GameManagerの初期化（2プレイヤーモード）
@@ -117,11 +161,8 @@ mod integration_tests {
             .get_valid_moves(manager.session.current_player);
 
         // デバッグ情報
-        println!("Cross position: {:?}", cross_pos);
         println!("Valid moves: {:?}", valid_moves);
-        println!("Current player: {:?}", manager.session.current_player);
-        println!("Board state:");
-        println!("{}", manager.session.board.display());
+        println!("{}", manager.session.debug_dump());
 
         assert_eq!(valid_moves.len(), 1, "Should have exactly one valid move");
 
@@ -165,10 +206,12 @@ mod integration_tests {
 
             // 移動先の駒の値を取得
             let target = valid_moves[0];
-            let piece_value = match manager.session.board.get_piece(target.0, target.1) {
-                Piece::Number(val) => val,
-                _ => 0,
-            };
+            let piece_value = manager
+                .session
+                .board
+                .get_piece(target.0, target.1)
+                .value()
+                .unwrap_or(0);
 
             // 移動を実行
             manager.make_move(target);
@@ -221,14 +264,12 @@ mod integration_tests {
         let before_score = manager.session.scores.get(&Player::First).unwrap().total;
 
         // 移動先の駒の値を取得
-        let piece_value = match manager
+        let piece_value = manager
             .session
             .board
             .get_piece(move_target.0, move_target.1)
-        {
-            Piece::Number(val) => val,
-            _ => 0,
-        };
+            .value()
+            .unwrap_or(0);
 
         // 移動を実行
         manager.make_move(move_target);
@@ -285,4 +326,1992 @@ mod integration_tests {
             micattix::core::MoveDirection::Vertical
         );
     }
+
+    #[test]
+    fn test_explain_best_move_prefers_denial() {
+        let mut board = empty_board_with_cross((0, 0));
+
+        // (0,1)の5を取ると次のプレイヤーが縦に8を取れる
+        board.set_piece(0, 1, Piece::Number(5));
+        board.set_piece(1, 1, Piece::Number(8));
+        // (0,2)の3を取ると次のプレイヤーは1しか取れない
+        board.set_piece(0, 2, Piece::Number(3));
+        board.set_piece(1, 2, Piece::Number(1));
+
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        let explanation = session.explain_best_move(Player::First).unwrap();
+
+        assert_eq!(explanation.target, (0, 2));
+        assert_eq!(explanation.immediate_value, 3);
+        assert_eq!(explanation.denies_opponent, Some(7));
+    }
+
+    #[test]
+    fn test_round_ended_reports_captured_pieces() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        board.set_piece(3, 2, Piece::Number(-2));
+
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        let events = Rc::new(RefCell::new(Vec::new()));
+        manager.add_listener(Box::new(SharedEventRecorder {
+            events: Rc::clone(&events),
+        }));
+
+        manager.start_game();
+        manager.make_move((0, 2));
+        manager.make_move((3, 2));
+
+        let events = events.borrow();
+        let pieces = events
+            .iter()
+            .find_map(|event| match event {
+                GameEvent::RoundEnded(_, _, pieces) => Some(pieces.clone()),
+                _ => None,
+            })
+            .expect("RoundEnded should be fired");
+
+        assert_eq!(pieces[&Player::First], vec![Piece::Number(5)]);
+        assert_eq!(pieces[&Player::Second], vec![Piece::Number(-2)]);
+    }
+
+    #[test]
+    fn test_set_game_mode() {
+        // 開始前であればモードを変更でき、プレイヤーとスコアも作り直される
+        let mut manager = GameManager::new(BoardSize::Small, GameMode::TwoPlayers);
+        assert!(manager.set_game_mode(GameMode::FourPlayers).is_ok());
+        assert_eq!(manager.session.game_mode, GameMode::FourPlayers);
+        assert_eq!(manager.session.players.len(), 4);
+        assert!(manager.session.scores.contains_key(&Player::Fourth));
+        assert!(manager.session.total_scores.contains_key(&Player::Fourth));
+    }
+
+    #[test]
+    fn test_set_game_mode_rejected_after_move() {
+        let mut manager = GameManager::new(BoardSize::Small, GameMode::TwoPlayers);
+
+        // 移動後はモード変更できない
+        let target = manager.session.board.get_valid_moves(Player::First)[0];
+        manager.make_move(target);
+
+        assert_eq!(
+            manager.set_game_mode(GameMode::FourPlayers),
+            Err(StateError::GameInProgress)
+        );
+        assert_eq!(manager.session.game_mode, GameMode::TwoPlayers);
+        assert_eq!(manager.session.players.len(), 2);
+    }
+
+    #[test]
+    fn test_moves_this_round() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        board.set_piece(3, 2, Piece::Number(2));
+        board.set_piece(3, 0, Piece::Number(1));
+
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        manager.add_listener(Box::new(StatsListener::new()));
+        manager.start_game();
+        assert_eq!(manager.session.moves_this_round(), 0);
+
+        manager.make_move((0, 2));
+        manager.make_move((3, 2));
+        manager.make_move((3, 0));
+        assert!(manager.session.is_round_over());
+        assert_eq!(manager.session.moves_this_round(), 3);
+
+        // 次のラウンドでは0に戻る
+        manager.start_next_round();
+        assert_eq!(manager.session.moves_this_round(), 0);
+    }
+
+    #[test]
+    fn test_stats_listener_round_lengths() {
+        let mut stats = StatsListener::new();
+        stats.on_event(GameEvent::RoundStarted(1));
+        for target in [(0, 2), (3, 2)] {
+            stats.on_event(GameEvent::MoveMade(Player::First, target, Piece::Number(1)));
+        }
+        stats.on_event(GameEvent::RoundEnded(None, HashMap::new(), HashMap::new()));
+
+        assert_eq!(stats.stats.round_lengths, vec![2]);
+    }
+
+    #[test]
+    fn test_try_start_next_round() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+
+        // ラウンド途中では開始できず、状態も変わらない
+        assert_eq!(
+            session.try_start_next_round(),
+            Err(StateError::RoundNotOver)
+        );
+        assert_eq!(session.round, 1);
+        assert_eq!(session.total_scores[&Player::First], 0);
+
+        // 盤面が空になれば開始できる
+        session.process_move((0, 2)).unwrap();
+        assert!(session.try_start_next_round().is_ok());
+        assert_eq!(session.round, 2);
+        assert_eq!(session.total_scores[&Player::First], 5);
+    }
+
+    #[test]
+    fn test_tie_break_most_rounds_won() {
+        let mut session = GameSession::new(BoardSize::Small, GameMode::TwoPlayers);
+        session.tie_break = TieBreak::MostRoundsWon;
+
+        // 合計は10対10だが、Secondは2ラウンド勝っている
+        finish_scripted_round(&mut session, &[(Player::First, &[10])]);
+        finish_scripted_round(&mut session, &[(Player::Second, &[5])]);
+        finish_scripted_round(&mut session, &[(Player::Second, &[5])]);
+
+        assert_eq!(session.total_scores[&Player::First], 10);
+        assert_eq!(session.total_scores[&Player::Second], 10);
+        assert_eq!(session.get_overall_winner(), Some(Player::Second));
+    }
+
+    #[test]
+    fn test_tie_break_none_keeps_draw() {
+        let mut session = GameSession::new(BoardSize::Small, GameMode::TwoPlayers);
+
+        finish_scripted_round(&mut session, &[(Player::First, &[10])]);
+        finish_scripted_round(&mut session, &[(Player::Second, &[5])]);
+        finish_scripted_round(&mut session, &[(Player::Second, &[5])]);
+
+        assert_eq!(session.tie_break, TieBreak::None);
+        assert_eq!(session.get_overall_winner(), None);
+    }
+
+    #[test]
+    fn test_stats_listener_average_capture() {
+        let mut stats = StatsListener::new();
+        stats.on_event(GameEvent::RoundStarted(1));
+
+        let moves = [
+            (Player::First, 5),
+            (Player::Second, 3),
+            (Player::First, -2),
+            (Player::Second, 4),
+            (Player::First, 6),
+        ];
+        let mut scores = HashMap::new();
+        for (player, value) in moves {
+            stats.on_event(GameEvent::MoveMade(player, (0, 0), Piece::Number(value)));
+            let score = scores.entry(player).or_insert(0);
+            *score += value;
+            stats.on_event(GameEvent::ScoreUpdated(player, *score));
+        }
+
+        assert_eq!(stats.stats.moves_total, 5);
+        // First: (5 - 2 + 6) / 3手
+        assert_eq!(stats.stats.average_capture_per_move(Player::First), 3.0);
+        assert_eq!(stats.stats.average_capture_per_move(Player::Second), 3.5);
+        // 手数0のプレイヤー
+        assert_eq!(stats.stats.average_capture_per_move(Player::Third), 0.0);
+
+        // Movement::Magnet では通過して取得した駒も集計する
+        let mut board = empty_board_with_cross((1, 0));
+        board.set_piece(1, 1, Piece::Number(2));
+        board.set_piece(1, 2, Piece::Number(-3));
+        board.set_piece(1, 3, Piece::Number(7));
+        board.set_piece(3, 3, Piece::Number(1));
+        board.rules.movement = Movement::Magnet;
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        let stats = Rc::new(RefCell::new(StatsListener::new()));
+        manager.add_shared_listener(stats.clone());
+        manager.start_game();
+        manager.make_move((1, 3));
+
+        let total = manager.session.scores[&Player::First].total;
+        assert_eq!(total, 2 - 3 + 7);
+        assert_eq!(
+            stats.borrow().stats.captured_by_player[&Player::First],
+            total
+        );
+        assert_eq!(
+            stats.borrow().stats.average_capture_per_move(Player::First),
+            total as f32
+        );
+    }
+
+    #[test]
+    fn test_event_channel() {
+        let mut manager = GameManager::new(BoardSize::Small, GameMode::TwoPlayers);
+        let receiver = manager.event_channel();
+
+        let target = manager.session.board.get_valid_moves(Player::First)[0];
+        manager.make_move(target);
+
+        match receiver.try_recv() {
+            Ok(GameEvent::MoveMade(player, moved_to, _)) => {
+                assert_eq!(player, Player::First);
+                assert_eq!(moved_to, target);
+            }
+            other => panic!("expected MoveMade, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_forfeited_player_cannot_move() {
+        let mut session = GameSession::new(BoardSize::Small, GameMode::FourPlayers);
+
+        // 手番のプレイヤーが棄権すると次のプレイヤーに手番が移る
+        session.forfeit(Player::First);
+        assert!(!session.players.contains(&Player::First));
+        assert_eq!(session.current_player, Player::Second);
+
+        // 棄権したプレイヤーの手は拒否される
+        session.current_player = Player::First;
+        let target = session.board.get_valid_moves(Player::First)[0];
+        assert_eq!(
+            session.process_move(target),
+            Err(MoveError::PlayerNotInGame(Player::First))
+        );
+    }
+
+    #[test]
+    fn test_forfeited_player_is_skipped() {
+        let mut session = GameSession::new(BoardSize::Small, GameMode::FourPlayers);
+        session.forfeit(Player::Second);
+
+        let target = session.board.get_valid_moves(Player::First)[0];
+        session.process_move(target).unwrap();
+        assert_eq!(session.current_player, Player::Third);
+    }
+
+    #[test]
+    fn test_describe_options() {
+        let mut board = empty_board_with_cross((1, 2));
+        board.set_piece(1, 0, Piece::Number(5));
+        board.set_piece(1, 3, Piece::Number(-1));
+
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        let description = session.describe_options();
+
+        assert!(description.contains("Cross at row 2, column 3."));
+        assert!(description.contains("First can move to:"));
+        assert!(description.contains("row 2, column 1 for 5"));
+        assert!(description.contains("row 2, column 4 for -1"));
+    }
+
+    #[test]
+    fn test_apply_event_log() {
+        let initial = GameSession::new(BoardSize::Small, GameMode::FourPlayers);
+        let mut manager = GameManager::new_with_board(initial.board.clone(), GameMode::FourPlayers);
+        let receiver = manager.event_channel();
+
+        // 無効な手を含めていくつか手を進める
+        manager.start_game();
+        manager.make_move((10, 10));
+        for _ in 0..5 {
+            let player = manager.session.current_player;
+            let valid_moves = manager.session.board.get_valid_moves(player);
+            if valid_moves.is_empty() {
+                break;
+            }
+            manager.make_move(valid_moves[0]);
+        }
+
+        let events: Vec<GameEvent> = receiver.try_iter().collect();
+        let rebuilt = GameManager::apply_event_log(initial, &events).unwrap();
+
+        assert_eq!(rebuilt.session.board.pieces, manager.session.board.pieces);
+        assert_eq!(
+            rebuilt.session.board.cross_position,
+            manager.session.board.cross_position
+        );
+        assert_eq!(
+            rebuilt.session.current_player,
+            manager.session.current_player
+        );
+        assert_eq!(rebuilt.session.history, manager.session.history);
+        for player in &manager.session.players {
+            assert_eq!(
+                rebuilt.session.scores[player].total,
+                manager.session.scores[player].total
+            );
+        }
+    }
+
+    #[test]
+    fn test_swap_sides_on_second_first_turn() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(8));
+        board.set_piece(2, 2, Piece::Number(3));
+        board.set_piece(2, 1, Piece::Number(4));
+        board.set_piece(3, 1, Piece::Number(1));
+
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        let receiver = manager.event_channel();
+
+        // 先手の前は交換できない
+        assert_eq!(manager.swap_sides(), Err(StateError::SwapNotAllowed));
+
+        manager.make_move((0, 2));
+        assert!(manager.swap_sides().is_ok());
+        assert!(receiver
+            .try_iter()
+            .any(|event| matches!(event, GameEvent::SidesSwapped)));
+
+        // 先手の取った8は後手のものになり、先手が縦方向で次の手を指す
+        assert_eq!(manager.session.scores[&Player::Second].total, 8);
+        assert_eq!(manager.session.scores[&Player::First].total, 0);
+        assert_eq!(manager.session.current_player, Player::First);
+        assert_eq!(manager.session.valid_moves(Player::First), vec![(2, 2)]);
+
+        manager.make_move((2, 2));
+        assert_eq!(manager.session.scores[&Player::First].total, 3);
+
+        // 後手は横方向に移動する
+        assert_eq!(manager.session.current_player, Player::Second);
+        manager.make_move((2, 1));
+        assert_eq!(manager.session.scores[&Player::Second].total, 12);
+
+        // 2回目以降は交換できない
+        assert_eq!(manager.swap_sides(), Err(StateError::SwapNotAllowed));
+    }
+
+    #[test]
+    fn test_target_score_ends_game() {
+        let mut manager = GameManager::new(BoardSize::Small, GameMode::TwoPlayers);
+        manager.session.target_score = Some(20);
+        let receiver = manager.event_channel();
+
+        // 1ラウンド目: First 12点
+        manager
+            .session
+            .scores
+            .get_mut(&Player::First)
+            .unwrap()
+            .add_piece(Piece::Number(12));
+        manager.start_next_round();
+        assert!(!receiver
+            .try_iter()
+            .any(|event| matches!(event, GameEvent::GameEnded(..))));
+
+        // 2ラウンド目: First 9点で合計21点
+        manager
+            .session
+            .scores
+            .get_mut(&Player::First)
+            .unwrap()
+            .add_piece(Piece::Number(9));
+        manager.start_next_round();
+
+        let events: Vec<GameEvent> = receiver.try_iter().collect();
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, GameEvent::GameEnded(Some(Player::First), _, _))));
+        assert!(!events.iter().any(|event| matches!(
+            event,
+            GameEvent::RoundStarted(_) | GameEvent::BoardGenerated(_)
+        )));
+        assert_eq!(manager.session.round, 2);
+    }
+
+    #[test]
+    fn test_current_leader_mid_round() {
+        let mut session = GameSession::new(BoardSize::Small, GameMode::TwoPlayers);
+
+        // 開始直後は同点
+        assert_eq!(session.current_leader(), None);
+
+        session
+            .scores
+            .get_mut(&Player::Second)
+            .unwrap()
+            .add_piece(Piece::Number(4));
+
+        // 盤面が残っていてもトップを返す
+        assert!(!session.is_round_over());
+        assert_eq!(session.get_round_winner(), None);
+        assert_eq!(session.current_leader(), Some(Player::Second));
+    }
+
+    #[test]
+    fn test_max_reachable_score() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 1, Piece::Number(5));
+        board.set_piece(1, 0, Piece::Number(7));
+        board.set_piece(2, 2, Piece::Number(-4));
+        board.set_piece(3, 3, Piece::Number(0));
+
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        session
+            .scores
+            .get_mut(&Player::First)
+            .unwrap()
+            .add_piece(Piece::Number(3));
+
+        // 現在の3点 + 残りの正の駒 5 + 7
+        assert_eq!(session.max_reachable_score(Player::First), 15);
+        assert_eq!(session.max_reachable_score(Player::Second), 12);
+    }
+
+    #[test]
+    fn test_shared_listener() {
+        let recorder = Rc::new(RefCell::new(SimpleEventRecorder::new()));
+        let stats = Rc::new(RefCell::new(StatsListener::new()));
+
+        let mut manager = GameManager::new(BoardSize::Small, GameMode::TwoPlayers);
+        manager.add_listener(Box::new(SharedListener::new(recorder.clone())));
+        manager.add_shared_listener(stats.clone());
+
+        manager.start_game();
+        let target = manager.session.valid_moves(Player::First)[0];
+        manager.make_move(target);
+
+        // 登録後も呼び出し側から同じリスナーの状態を参照できる
+        assert!(matches!(
+            recorder.borrow().events.front(),
+            Some(GameEvent::GameStarted)
+        ));
+        assert!(recorder.borrow().events.iter().any(
+            |event| matches!(event, GameEvent::MoveMade(Player::First, t, _) if *t == target)
+        ));
+        assert_eq!(stats.borrow().stats.moves_total, 1);
+    }
+
+    #[test]
+    fn test_explain_illegal() {
+        let mut board = empty_board_with_cross((1, 1));
+        board.set_piece(1, 3, Piece::Number(4));
+        board.set_piece(3, 3, Piece::Number(6));
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+
+        // Firstは横方向に移動する
+        assert_eq!(session.explain_illegal((1, 3)), None);
+        assert_eq!(
+            session.explain_illegal((1, 1)),
+            Some(MoveError::IsCrossPosition((1, 1)))
+        );
+        assert_eq!(
+            session.explain_illegal((1, 2)),
+            Some(MoveError::EmptyCell((1, 2)))
+        );
+        assert_eq!(
+            session.explain_illegal((3, 3)),
+            Some(MoveError::NotOnLine((3, 3)))
+        );
+        assert_eq!(
+            session.explain_illegal((1, 4)),
+            Some(MoveError::OutOfBounds((1, 4)))
+        );
+    }
+
+    #[test]
+    fn test_debug_dump() {
+        let board = empty_board_with_cross((2, 1));
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        session.round = 3;
+
+        let dump = session.debug_dump();
+        assert!(dump.contains("Round: 3"));
+        assert!(dump.contains("Cross position: (2, 1)"));
+        assert!(dump.contains("Current player: First"));
+    }
+
+    #[test]
+    fn test_round_over_when_only_forbidden_negatives_remain() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(-3));
+        board.rules.allow_negative_capture = false;
+
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+
+        // 負の駒は残っているが移動先がないのでラウンド終了
+        assert!(!session.board.is_game_over());
+        assert!(session.valid_moves(Player::First).is_empty());
+        assert!(session.is_round_over());
+    }
+
+    #[test]
+    fn test_game_view() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        board.set_piece(3, 2, Piece::Number(2));
+
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        manager.start_game();
+        manager.make_move((0, 2));
+
+        let view = manager.current_state();
+        assert_eq!(view.board().cross_position, (0, 2));
+        assert_eq!(view.current_player(), Player::Second);
+        assert_eq!(view.round(), 1);
+        assert_eq!(view.players(), &[Player::First, Player::Second]);
+        assert_eq!(view.score(Player::First), 5);
+        assert_eq!(view.score(Player::Second), 0);
+        assert_eq!(view.score(Player::Third), 0);
+        assert_eq!(view.total_score(Player::First), 0);
+        assert_eq!(view.valid_moves(), vec![(3, 2)]);
+    }
+
+    #[test]
+    fn test_track_pieces_disabled() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        board.set_piece(3, 2, Piece::Number(-2));
+        board.set_piece(3, 0, Piece::Number(4));
+
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        session.track_pieces = false;
+
+        session.process_move((0, 2)).unwrap();
+        session.process_move((3, 2)).unwrap();
+        session.process_move((3, 0)).unwrap();
+
+        // 得点は正しく加算されるが駒は記録されない
+        assert_eq!(session.scores[&Player::First].total, 9);
+        assert_eq!(session.scores[&Player::Second].total, -2);
+        assert!(session.scores[&Player::First].pieces.is_empty());
+        assert!(session.scores[&Player::Second].pieces.is_empty());
+
+        session.start_next_round();
+        assert_eq!(session.total_scores[&Player::First], 9);
+        assert!(session.total_pieces[&Player::First].is_empty());
+    }
+
+    #[test]
+    fn test_replay_notation_round_trip() {
+        let seed = 42;
+        let mut session = GameSession::new_with_board(
+            Board::new_seeded(BoardSize::Small, seed),
+            GameMode::TwoPlayers,
+        );
+        while !session.is_round_over() {
+            let target = session.valid_moves(session.current_player)[0];
+            session.process_move(target).unwrap();
+        }
+
+        let replay = Replay::record(seed, &session);
+        assert!(!replay.moves.is_empty());
+
+        let notation = replay.to_notation();
+        assert!(notation.starts_with('r'));
+        let parsed =
+            Replay::from_notation(seed, BoardSize::Small, GameMode::TwoPlayers, &notation).unwrap();
+        assert_eq!(parsed, replay);
+
+        // 再生した結果も元のセッションと一致する
+        let replayed = parsed.play().unwrap();
+        assert_eq!(replayed.board.pieces, session.board.pieces);
+        assert_eq!(
+            replayed.scores[&Player::First].total,
+            session.scores[&Player::First].total
+        );
+
+        assert_eq!(
+            Replay::from_notation(seed, BoardSize::Small, GameMode::TwoPlayers, "r1c2 x9"),
+            Err(ReplayError::InvalidNotation("x9".to_string()))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_replay_json_round_trip() {
+        let mut replay = Replay::new(7, BoardSize::Large, GameMode::FourPlayers);
+        replay.push((0, 3));
+        replay.push((4, 3));
+
+        let json = serde_json::to_string(&replay).unwrap();
+        let parsed: Replay = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, replay);
+    }
+
+    #[test]
+    fn test_score_updated_event() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        board.set_piece(3, 2, Piece::Number(-2));
+        board.set_piece(3, 0, Piece::Number(4));
+
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        let receiver = manager.event_channel();
+        manager.start_game();
+        manager.make_move((0, 2));
+        manager.make_move((3, 2));
+        manager.make_move((3, 0));
+
+        let updates: Vec<(Player, i32)> = receiver
+            .try_iter()
+            .filter_map(|event| match event {
+                GameEvent::ScoreUpdated(player, score) => Some((player, score)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            updates,
+            vec![(Player::First, 5), (Player::Second, -2), (Player::First, 9)]
+        );
+        assert_eq!(manager.session.scores[&Player::First].total, 9);
+    }
+
+    #[test]
+    fn test_session_with_state() {
+        let mut first = PlayerScore::new();
+        first.add_piece(Piece::Number(6));
+        let mut second = PlayerScore::new();
+        second.add_piece(Piece::Number(3));
+        second.add_piece(Piece::Number(-1));
+        let scores = HashMap::from([(Player::First, first), (Player::Second, second)]);
+
+        let board = empty_board_with_cross((1, 1));
+        let session = GameSession::with_state(
+            board.clone(),
+            GameMode::TwoPlayers,
+            Player::Second,
+            scores.clone(),
+        )
+        .unwrap();
+        assert_eq!(session.current_player, Player::Second);
+        assert_eq!(session.scores[&Player::First].total, 6);
+        assert_eq!(session.scores[&Player::Second].total, 2);
+        assert_eq!(session.scores[&Player::Second].pieces.len(), 2);
+
+        // 4人モードの参加者とは一致しない
+        assert_eq!(
+            GameSession::with_state(
+                board.clone(),
+                GameMode::FourPlayers,
+                Player::First,
+                scores.clone()
+            )
+            .err(),
+            Some(StateError::PlayerMismatch)
+        );
+        // 参加していないプレイヤーの手番は指定できない
+        assert_eq!(
+            GameSession::with_state(board, GameMode::TwoPlayers, Player::Third, scores).err(),
+            Some(StateError::PlayerMismatch)
+        );
+    }
+
+    #[test]
+    fn test_replay_first_divergence() {
+        let base =
+            Replay::from_notation(3, BoardSize::Small, GameMode::TwoPlayers, "r1c2 r3c2 r3c4")
+                .unwrap();
+        let branched =
+            Replay::from_notation(3, BoardSize::Small, GameMode::TwoPlayers, "r1c2 r3c2 r3c1")
+                .unwrap();
+        let shorter =
+            Replay::from_notation(3, BoardSize::Small, GameMode::TwoPlayers, "r1c2").unwrap();
+
+        assert_eq!(base.first_divergence(&branched), Ok(Some(2)));
+        assert_eq!(base.first_divergence(&shorter), Ok(Some(1)));
+        assert_eq!(base.first_divergence(&base.clone()), Ok(None));
+
+        let other_seed = Replay::new(4, BoardSize::Small, GameMode::TwoPlayers);
+        assert_eq!(
+            base.first_divergence(&other_seed),
+            Err(ReplayError::Incompatible)
+        );
+    }
+
+    #[test]
+    fn test_round_winners_history() {
+        let mut session = GameSession::new(BoardSize::Small, GameMode::TwoPlayers);
+        assert!(session.round_winners_history().is_empty());
+
+        finish_scripted_round(
+            &mut session,
+            &[(Player::First, &[5]), (Player::Second, &[3])],
+        );
+        finish_scripted_round(
+            &mut session,
+            &[(Player::First, &[2]), (Player::Second, &[2])],
+        );
+        finish_scripted_round(
+            &mut session,
+            &[(Player::First, &[1]), (Player::Second, &[7])],
+        );
+
+        assert_eq!(
+            session.round_winners_history(),
+            &[Some(Player::First), None, Some(Player::Second)]
+        );
+    }
+
+    #[test]
+    fn test_undo_round() {
+        let initial = Board::new_seeded(BoardSize::Small, 9);
+        let mut manager = GameManager::new_with_board(initial.clone(), GameMode::TwoPlayers);
+        manager.session.total_scores.insert(Player::First, 12);
+        manager.start_game();
+
+        for _ in 0..3 {
+            let current = manager.session.current_player;
+            let target = manager.session.valid_moves(current)[0];
+            manager.make_move(target);
+        }
+        assert_eq!(manager.session.moves_this_round(), 3);
+
+        manager.undo_round();
+
+        // 盤面は開始時の状態に戻り、ラウンドの得点は0になる
+        assert_eq!(manager.session.board, initial);
+        assert_eq!(
+            manager.session.board.piece_counts().values().sum::<usize>(),
+            15
+        );
+        assert!(manager
+            .session
+            .scores
+            .values()
+            .all(|score| score.total == 0));
+        assert_eq!(manager.session.current_player, Player::First);
+        assert_eq!(manager.session.moves_this_round(), 0);
+
+        // 合計得点とラウンド番号は変わらない
+        assert_eq!(manager.session.total_scores[&Player::First], 12);
+        assert_eq!(manager.session.round, 1);
+
+        // 手がなければ何もしない
+        manager.undo_round();
+        assert_eq!(manager.session.board, initial);
+    }
+
+    #[test]
+    fn test_ai_best_move_timed() {
+        use std::time::Duration;
+
+        // 持ち時間がなくても合法手を返す
+        let session = GameSession::new_with_board(
+            Board::new_seeded(BoardSize::Large, 5),
+            GameMode::TwoPlayers,
+        );
+        let target = ai::best_move_timed(&session, Player::First, Duration::ZERO).unwrap();
+        assert!(session.valid_moves(Player::First).contains(&target));
+
+        // 十分な持ち時間があれば固定深さの完全探索と一致する
+        let board = Board::from_ascii(
+            "X  3 -2  5\n\
+             4  .  1  .\n\
+             . -1  .  6\n\
+             2  .  7  .\n",
+        )
+        .unwrap();
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        let deep = ai::best_move(&session, Player::First, 16);
+        assert!(deep.is_some());
+        assert_eq!(
+            ai::best_move_timed(&session, Player::First, Duration::from_secs(30)),
+            deep
+        );
+
+        // 手番でないプレイヤーの手は探索しない
+        assert_eq!(ai::best_move(&session, Player::Second, 3), None);
+    }
+
+    #[test]
+    fn test_turn_order() {
+        let mut session = GameSession::new(BoardSize::Small, GameMode::FourPlayers);
+        assert_eq!(
+            session.turn_order(),
+            vec![Player::First, Player::Second, Player::Third, Player::Fourth]
+        );
+
+        // 2ラウンド目はSecondが先手
+        finish_scripted_round(&mut session, &[(Player::First, &[3])]);
+        assert_eq!(
+            session.turn_order(),
+            vec![Player::Second, Player::Third, Player::Fourth, Player::First]
+        );
+
+        // 手が進んでもラウンドの先手から数える
+        let target = session.valid_moves(Player::Second)[0];
+        session.process_move(target).unwrap();
+        assert_eq!(session.turn_order()[0], Player::Second);
+    }
+
+    #[test]
+    fn test_extra_turn_bonus() {
+        let board = Board::from_ascii(
+            "X + . 5\n\
+             . . . .\n\
+             . 4 . 3\n\
+             . . . .\n",
+        )
+        .unwrap();
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        let receiver = manager.event_channel();
+        manager.start_game();
+
+        manager.make_move((0, 1));
+
+        // 特殊マスは得点にならず、同じプレイヤーがもう一度移動する
+        assert_eq!(manager.session.current_player, Player::First);
+        assert_eq!(manager.session.scores[&Player::First].total, 0);
+        assert_eq!(manager.session.board.get_piece(0, 1), Piece::Cross);
+        assert!(!manager.session.is_round_over());
+
+        let events: Vec<GameEvent> = receiver.try_iter().collect();
+        assert!(events.iter().any(|event| matches!(
+            event,
+            GameEvent::MoveMade(Player::First, (0, 1), Piece::Bonus(BonusKind::ExtraTurn))
+        )));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, GameEvent::ExtraTurnGranted(Player::First))));
+        assert!(!events
+            .iter()
+            .any(|event| matches!(event, GameEvent::ScoreUpdated(..))));
+
+        manager.make_move((0, 3));
+        assert_eq!(manager.session.current_player, Player::Second);
+        assert_eq!(manager.session.scores[&Player::First].total, 5);
+    }
+
+    #[test]
+    fn test_move_after_round_over_is_rejected() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 3, Piece::Number(4));
+
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        let receiver = manager.event_channel();
+        manager.start_game();
+        manager.make_move((0, 3));
+        assert!(manager.session.is_round_over());
+        receiver.try_iter().for_each(drop);
+
+        manager.make_move((1, 3));
+
+        let events: Vec<GameEvent> = receiver.try_iter().collect();
+        assert_eq!(events.len(), 1);
+        match &events[0] {
+            GameEvent::InvalidMove(Player::Second, (1, 3), reason) => {
+                assert_eq!(reason, &MoveError::RoundOver.to_string());
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+        assert_eq!(
+            manager.session.process_move((1, 3)),
+            Err(MoveError::RoundOver)
+        );
+    }
+
+    #[test]
+    fn test_custom_board_generator() {
+        use rand::RngCore;
+
+        // 全て1の駒とクロスチップ1つの盤面を作る生成方法
+        #[derive(Debug)]
+        struct OnesGenerator;
+
+        impl BoardGenerator for OnesGenerator {
+            fn generate(&self, size: BoardSize, rng: &mut dyn RngCore) -> Board {
+                let (rows, cols) = size.dimensions();
+                let mut pieces = vec![Piece::Number(1); rows * cols - 1];
+                pieces.push(Piece::Cross);
+
+                let mut board = Board::new_seeded(size, 0);
+                board.place_pieces(pieces, rng);
+                board
+            }
+        }
+
+        let mut session =
+            GameSession::new_with_generator(BoardSize::Small, GameMode::TwoPlayers, OnesGenerator);
+        assert_eq!(
+            session.board.piece_counts().into_iter().collect::<Vec<_>>(),
+            vec![(1, 15)]
+        );
+        assert_eq!(
+            session.board.get_piece(
+                session.board.cross_position.0,
+                session.board.cross_position.1
+            ),
+            Piece::Cross
+        );
+
+        // 次のラウンドも同じ生成方法を使う
+        session.start_next_round();
+        assert_eq!(
+            session.board.piece_counts().into_iter().collect::<Vec<_>>(),
+            vec![(1, 15)]
+        );
+    }
+
+    #[test]
+    fn test_score_delta() {
+        let mut board = empty_board_with_cross((1, 1));
+        board.set_piece(1, 3, Piece::Number(7));
+        board.set_piece(3, 1, Piece::Number(-4));
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+
+        assert_eq!(session.score_delta(Player::First, (1, 3)), Ok(7));
+        assert_eq!(session.score_delta(Player::Second, (3, 1)), Ok(-4));
+        assert!(session.score_delta(Player::First, (3, 1)).is_err());
+        assert!(session.score_delta(Player::First, (1, 1)).is_err());
+    }
+
+    #[test]
+    fn test_max_rounds_ends_game() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        manager.session.max_rounds = Some(1);
+        let receiver = manager.event_channel();
+        assert!(!manager.session.is_game_over());
+
+        // 後手に移動先がなくなり1ラウンド目が終了する
+        manager.make_move((0, 2));
+        assert!(manager.session.is_game_over());
+        assert_eq!(manager.session.total_scores[&Player::First], 5);
+
+        let events: Vec<GameEvent> = receiver.try_iter().collect();
+        assert!(matches!(
+            events.last(),
+            Some(GameEvent::GameEnded(Some(Player::First), _, _))
+        ));
+
+        // 終了したゲームは次のラウンドに進まない
+        manager.start_next_round();
+        assert_eq!(manager.session.round, 1);
+    }
+
+    #[test]
+    fn test_target_score_is_game_over() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        session.total_scores.insert(Player::First, 16);
+        session.target_score = Some(20);
+        assert!(!session.is_game_over());
+
+        // 現在のラウンドの得点を含めて目標に達する
+        session.process_move((0, 2)).unwrap();
+        assert!(session.is_round_over());
+        assert!(session.is_game_over());
+
+        session.target_score = Some(30);
+        assert!(!session.is_game_over());
+    }
+
+    #[test]
+    fn test_validate_sequence_reports_first_illegal_move() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(3));
+        board.set_piece(2, 2, Piece::Number(4));
+        board.set_piece(2, 3, Piece::Number(5));
+
+        let legal = [(0, 2), (2, 2), (2, 3)];
+        assert_eq!(
+            validate_sequence(&board, GameMode::TwoPlayers, &legal),
+            Ok(())
+        );
+
+        // 2手目は後手の縦方向の列にない
+        let illegal = [(0, 2), (2, 3), (2, 2)];
+        assert_eq!(
+            validate_sequence(&board, GameMode::TwoPlayers, &illegal),
+            Err((1, MoveError::InvalidTarget((2, 3))))
+        );
+
+        let replay =
+            Replay::from_notation(3, BoardSize::Small, GameMode::TwoPlayers, "r1c1").unwrap();
+        assert!(matches!(
+            replay.validate(),
+            Err(ReplayError::IllegalMove(0, _))
+        ));
+    }
+
+    #[test]
+    fn test_solve_near_terminal_position() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 1, Piece::Number(5));
+        board.set_piece(0, 2, Piece::Number(1));
+        board.set_piece(3, 1, Piece::Number(9));
+        board.set_piece(1, 2, Piece::Number(2));
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        session
+            .scores
+            .get_mut(&Player::First)
+            .unwrap()
+            .add_piece(Piece::Number(3));
+
+        // 5を取ると後手に9を取られる（-4）ので、1を取って後手に2を取らせる（-1）のが最善
+        assert_eq!(ai::solve(&session), (3 - 1, Some((0, 2))));
+
+        let mut finished = session.clone();
+        finished.process_move((0, 2)).unwrap();
+        finished.process_move((1, 2)).unwrap();
+        assert_eq!(ai::solve(&finished), (3 + 1 - 2, None));
+    }
+
+    #[test]
+    fn test_next_player() {
+        let manager = GameManager::new(BoardSize::Small, GameMode::TwoPlayers);
+        assert_eq!(manager.next_player(), Player::Second);
+
+        let mut session = GameSession::new(BoardSize::Large, GameMode::FourPlayers);
+        session.current_player = Player::Second;
+        assert_eq!(session.next_player(), Player::Third);
+        session.current_player = Player::Fourth;
+        assert_eq!(session.next_player(), Player::First);
+
+        // 棄権したプレイヤーは飛ばす
+        session.current_player = Player::Second;
+        session.forfeit(Player::Third);
+        assert_eq!(session.next_player(), Player::Fourth);
+    }
+
+    #[test]
+    fn test_end_game_returns_game_result() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        manager.start_game();
+
+        // 1ラウンド目: Firstが5を取って駒がなくなる
+        manager.make_move((0, 2));
+        manager.start_next_round();
+
+        // 2ラウンド目: Secondが3を取り、Firstの移動先がなくなる
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(2, 0, Piece::Number(3));
+        board.set_piece(1, 1, Piece::Number(4));
+        manager.session.board = board;
+        assert_eq!(manager.session.current_player, Player::Second);
+        manager.make_move((2, 0));
+        assert!(manager.session.is_round_over());
+
+        let result = manager.end_game();
+        assert_eq!(result.rounds.len(), 2);
+        assert_eq!(result.rounds[0].reason, RoundEndReason::BoardCleared);
+        assert_eq!(result.rounds[1].reason, RoundEndReason::NoValidMoves);
+        assert_eq!(result.rounds[1].winner, Some(Player::Second));
+        assert_eq!(result.total_scores[&Player::First], 5);
+        assert_eq!(result.total_scores[&Player::Second], 3);
+        assert_eq!(result.overall_winner, Some(Player::First));
+    }
+
+    #[test]
+    fn test_puzzle_with_empty_cells() {
+        let board = Board::new_puzzle("3 . X .\n. . 2 .\n. 1 . .\n. . 5 4\n").unwrap();
+        assert!(!board.is_game_over());
+        assert_eq!(board.get_valid_moves(Player::First), vec![(0, 0)]);
+        assert_eq!(board.get_valid_moves(Player::Second), vec![(1, 2), (3, 2)]);
+
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        session.process_move((0, 0)).unwrap();
+        assert_eq!(session.scores[&Player::First].total, 3);
+        assert!(session.is_round_over());
+
+        assert_eq!(
+            Board::new_puzzle("3 . X .\n. . 2 .\n. X . .\n. . 5 4\n").unwrap_err(),
+            BoardError::CrossCount(2)
+        );
+    }
+
+    #[test]
+    fn test_round_ended_notified_once() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        board.set_piece(3, 3, Piece::Number(1));
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        let receiver = manager.event_channel();
+
+        manager.make_move((0, 2));
+        assert!(manager.session.is_round_over());
+        // ラウンド終了後の入力は無効な手として扱われ、RoundEndedは再通知されない
+        manager.make_move((3, 3));
+
+        let round_ended = receiver
+            .try_iter()
+            .filter(|event| matches!(event, GameEvent::RoundEnded(..)))
+            .count();
+        assert_eq!(round_ended, 1);
+    }
+
+    #[test]
+    fn test_claimed_points() {
+        // 4x4の盤面は正の駒だけなので、取得済みと残りの合計は常に一定
+        let mut session = GameSession::new_with_board(
+            Board::new_seeded(BoardSize::Small, 21),
+            GameMode::TwoPlayers,
+        );
+        let total = session.board.remaining_positive();
+        assert_eq!(session.claimed_points(), 0);
+
+        for _ in 0..4 {
+            let target = session.valid_moves(session.current_player)[0];
+            session.process_move(target).unwrap();
+            assert_eq!(
+                session.claimed_points() + session.board.remaining_positive(),
+                total
+            );
+        }
+        assert!(session.claimed_points() > 0);
+    }
+
+    #[test]
+    fn test_direction_override() {
+        let mut session = GameSession::new_with_board(
+            Board::with_cross_at(BoardSize::Large, 8, (2, 3)).unwrap(),
+            GameMode::TwoPlayers,
+        );
+        session
+            .directions
+            .insert(Player::First, MoveDirection::Vertical);
+
+        let moves = session.valid_moves(Player::First);
+        assert!(!moves.is_empty());
+        assert!(moves.iter().all(|&(_, col)| col == 3));
+        assert_eq!(
+            moves,
+            session
+                .board
+                .get_valid_moves_for_direction(MoveDirection::Vertical)
+        );
+        assert_eq!(
+            session.direction_of(Player::Second),
+            MoveDirection::Vertical
+        );
+    }
+
+    #[test]
+    fn test_replay_state_at_matches_iter_states() {
+        let mut session = GameSession::new_with_board(
+            Board::new_seeded(BoardSize::Small, 13),
+            GameMode::TwoPlayers,
+        );
+        for _ in 0..5 {
+            let target = session.valid_moves(session.current_player)[0];
+            session.process_move(target).unwrap();
+        }
+        let replay = Replay::record(13, &session);
+
+        let states: Vec<GameSession> = replay.iter_states().collect();
+        assert_eq!(states.len(), 6);
+        for (n, expected) in states.iter().enumerate() {
+            let state = replay.state_at(n).unwrap();
+            assert_eq!(state.board, expected.board);
+            assert_eq!(state.history, expected.history);
+            assert_eq!(state.current_player, expected.current_player);
+        }
+        assert_eq!(states[5].board, session.board);
+        assert!(matches!(
+            replay.state_at(6),
+            Err(ReplayError::IndexOutOfRange(6))
+        ));
+    }
+
+    #[test]
+    fn test_repetition_detection() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        board.set_piece(2, 2, Piece::Number(1));
+        let start = board.clone();
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        session.repetition_draw = true;
+
+        // 駒を取らない変種を想定し、盤面を巻き戻して同じ手を繰り返す
+        for _ in 0..2 {
+            session.process_move((0, 2)).unwrap();
+            assert!(!session.is_repetition());
+            session.board = start.clone();
+            session.current_player = Player::First;
+        }
+        session.process_move((0, 2)).unwrap();
+        assert!(session.is_repetition());
+
+        // 引き分けとしてラウンドが終わる
+        assert!(session.is_round_over());
+        assert_eq!(session.get_round_winner(), None);
+
+        // 無効な場合は局面を数えない
+        let mut session = GameSession::new_with_board(start.clone(), GameMode::TwoPlayers);
+        for _ in 0..3 {
+            session.process_move((0, 2)).unwrap();
+            session.board = start.clone();
+            session.current_player = Player::First;
+        }
+        assert!(!session.is_repetition());
+    }
+
+    #[test]
+    fn test_magnet_captures_passed_pieces() {
+        let mut board = empty_board_with_cross((1, 0));
+        board.set_piece(1, 1, Piece::Number(2));
+        board.set_piece(1, 2, Piece::Number(-3));
+        board.set_piece(1, 3, Piece::Number(7));
+        board.set_piece(3, 3, Piece::Number(1));
+        board.rules.movement = Movement::Magnet;
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+
+        assert_eq!(session.score_delta(Player::First, (1, 3)), Ok(2 - 3 + 7));
+        session.process_move((1, 3)).unwrap();
+        assert_eq!(session.scores[&Player::First].total, 6);
+        assert_eq!(session.scores[&Player::First].pieces.len(), 3);
+        assert_eq!(session.board.get_piece(1, 1), Piece::Empty);
+        assert_eq!(session.board.get_piece(1, 2), Piece::Empty);
+
+        // やり直すと通過した駒も元に戻る
+        session.undo_round();
+        assert_eq!(session.board.get_piece(1, 1), Piece::Number(2));
+        assert_eq!(session.board.get_piece(1, 2), Piece::Number(-3));
+    }
+
+    #[test]
+    fn test_slide_to_first_rule_blocks_jumps() {
+        let mut board = empty_board_with_cross((1, 0));
+        board.set_piece(1, 2, Piece::Number(2));
+        board.set_piece(1, 3, Piece::Number(7));
+        board.rules.movement = Movement::SlideToFirst;
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+
+        assert_eq!(session.valid_moves(Player::First), vec![(1, 2)]);
+        assert_eq!(
+            session.explain_illegal((1, 3)),
+            Some(MoveError::Blocked((1, 3)))
+        );
+        assert_eq!(
+            session.process_move((1, 3)),
+            Err(MoveError::Blocked((1, 3)))
+        );
+    }
+
+    #[test]
+    fn test_pieces_remaining() {
+        let mut session = GameSession::new_with_board(
+            Board::new_seeded(BoardSize::Small, 17),
+            GameMode::TwoPlayers,
+        );
+        assert_eq!(session.pieces_remaining(), 15);
+
+        for expected in (12..15).rev() {
+            let target = session.valid_moves(session.current_player)[0];
+            session.process_move(target).unwrap();
+            assert_eq!(session.pieces_remaining(), expected);
+        }
+
+        // 誰も移動できなければ0
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(1, 1, Piece::Number(4));
+        let stuck = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        assert_eq!(stuck.pieces_remaining(), 0);
+    }
+
+    #[test]
+    fn test_winrate_self_play() {
+        let rates = ai::winrate(BoardSize::Small, GameMode::TwoPlayers, 6, 100);
+        assert_eq!(rates.len(), 2);
+
+        // 引き分けの分だけ合計は1を下回る
+        let total: f32 = rates.values().sum();
+        assert!(total > 0.0 && total <= 1.0 + f32::EPSILON);
+        assert!(rates.values().all(|rate| (0.0..=1.0).contains(rate)));
+        assert_eq!(
+            rates,
+            ai::winrate(BoardSize::Small, GameMode::TwoPlayers, 6, 100)
+        );
+    }
+
+    #[test]
+    fn test_opening_book_move() {
+        let board = Board::new_seeded(BoardSize::Small, 31);
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        let searched = ai::best_move(&session, Player::First, 2).unwrap();
+
+        // 探索結果とは別の合法手を定跡として登録する
+        let booked = *session
+            .valid_moves(Player::First)
+            .iter()
+            .find(|target| **target != searched)
+            .unwrap();
+        ai::set_opening_book(HashMap::from([(session.board.zobrist_hash(), booked)]));
+
+        assert_eq!(ai::best_move(&session, Player::First, 2), Some(booked));
+        assert_eq!(
+            ai::best_move_timed(
+                &session,
+                Player::First,
+                std::time::Duration::from_millis(50)
+            ),
+            Some(booked)
+        );
+
+        ai::set_opening_book(HashMap::new());
+        assert_eq!(ai::best_move(&session, Player::First, 2), Some(searched));
+    }
+
+    #[test]
+    fn test_two_player_lead() {
+        let mut session = GameSession::new(BoardSize::Small, GameMode::TwoPlayers);
+        assert_eq!(session.two_player_lead(), None);
+
+        session
+            .scores
+            .get_mut(&Player::First)
+            .unwrap()
+            .add_piece(Piece::Number(3));
+        session
+            .scores
+            .get_mut(&Player::Second)
+            .unwrap()
+            .add_piece(Piece::Number(7));
+        assert_eq!(session.two_player_lead(), Some((Player::Second, 4)));
+
+        let mut four = GameSession::new(BoardSize::Large, GameMode::FourPlayers);
+        four.scores
+            .get_mut(&Player::Third)
+            .unwrap()
+            .add_piece(Piece::Number(5));
+        assert_eq!(four.two_player_lead(), None);
+    }
+
+    #[test]
+    fn test_transaction_rolls_back_on_error() {
+        let mut session = GameSession::new_with_board(
+            Board::new_seeded(BoardSize::Small, 8),
+            GameMode::TwoPlayers,
+        );
+        let board = session.board.clone();
+        let target = session.valid_moves(Player::First)[0];
+
+        let result = session.transaction(|s| {
+            s.process_move(target)?;
+            Err(MoveError::RoundOver)
+        });
+        assert_eq!(result, Err(MoveError::RoundOver));
+        assert_eq!(session.board, board);
+        assert_eq!(session.current_player, Player::First);
+        assert_eq!(session.scores[&Player::First].total, 0);
+        assert!(session.history.is_empty());
+
+        // 成功すれば変更はそのまま残る
+        assert_eq!(session.transaction(|s| s.process_move(target)), Ok(()));
+        assert_eq!(session.history.len(), 1);
+    }
+
+    #[test]
+    fn test_count_winning_lines() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        board.set_piece(0, 3, Piece::Number(-1));
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+
+        // 5を取れば勝ち、-1を取れば負け
+        assert_eq!(session.count_winning_lines(Player::First, 3), 1);
+        assert_eq!(session.count_winning_lines(Player::Second, 3), 1);
+        assert_eq!(session.count_winning_lines(Player::First, 0), 0);
+    }
+
+    #[test]
+    fn test_board_generated_precedes_round_started() {
+        let mut manager = GameManager::new(BoardSize::Small, GameMode::TwoPlayers);
+        let receiver = manager.event_channel();
+        manager.start_game();
+
+        let events: Vec<GameEvent> = receiver.try_iter().collect();
+        let generated = events
+            .iter()
+            .position(|e| matches!(e, GameEvent::BoardGenerated(_)))
+            .expect("BoardGenerated should be fired");
+        let started = events
+            .iter()
+            .position(|e| matches!(e, GameEvent::RoundStarted(1)))
+            .expect("RoundStarted should be fired");
+        assert!(generated < started);
+
+        // スナップショットには十字がちょうど1つある
+        match &events[generated] {
+            GameEvent::BoardGenerated(board) => {
+                let crosses = board.pieces.iter().filter(|p| **p == Piece::Cross).count();
+                assert_eq!(crosses, 1);
+                assert_eq!(*board, manager.session.board);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_forced_max_capture() {
+        let mut board = Board::new_puzzle("3 X 7 -2\n. . . .\n. 1 . .\n. 9 . .\n").unwrap();
+        board.rules.forced_max_capture = true;
+
+        // 最も得点の高いマスだけが候補になる
+        assert_eq!(board.get_valid_moves(Player::First), vec![(0, 2)]);
+        assert_eq!(board.get_valid_moves(Player::Second), vec![(3, 1)]);
+
+        // それ以外の移動先は拒否される
+        let mut rejected = board.clone();
+        assert_eq!(
+            rejected.make_move(Player::First, (0, 0)),
+            Err(MoveError::ForcedCapture((0, 0)))
+        );
+        assert_eq!(rejected, board);
+
+        assert_eq!(board.make_move(Player::First, (0, 2)), Ok(Piece::Number(7)));
+
+        // ルールが無効なら全ての駒が候補になる
+        let mut free = Board::new_puzzle("3 X 7 -2\n. . . .\n. 1 . .\n. 9 . .\n").unwrap();
+        free.rules.forced_max_capture = false;
+        assert_eq!(
+            free.get_valid_moves(Player::First),
+            vec![(0, 0), (0, 2), (0, 3)]
+        );
+    }
+
+    #[test]
+    fn test_move_menu() {
+        let board = Board::new_puzzle("3 X 7 -2\n. . 4 .\n. 1 . .\n. 9 -1 .\n").unwrap();
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+
+        // 番号は1始まりで、valid_moves と同じ順序になる
+        assert_eq!(
+            session.move_menu(),
+            vec![(1, (0, 0), 3), (2, (0, 2), 7), (3, (0, 3), -2)]
+        );
+
+        session.process_move((0, 2)).unwrap();
+        assert_eq!(session.current_player, Player::Second);
+        assert_eq!(session.move_menu(), vec![(1, (1, 2), 4), (2, (3, 2), -1)]);
+    }
+
+    #[test]
+    fn test_set_current_player() {
+        let mut session = GameSession::new(BoardSize::Small, GameMode::TwoPlayers);
+        assert_eq!(
+            session.set_current_player(Player::Third),
+            Err(StateError::NotInGame(Player::Third))
+        );
+        assert_eq!(session.current_player, Player::First);
+        assert_eq!(session.set_current_player(Player::Second), Ok(()));
+        assert_eq!(session.current_player, Player::Second);
+
+        let mut session = GameSession::new(BoardSize::Small, GameMode::FourPlayers);
+        assert_eq!(session.set_current_player(Player::Third), Ok(()));
+        assert_eq!(session.current_player, Player::Third);
+
+        // 棄権したプレイヤーも拒否される
+        session.forfeit(Player::Fourth);
+        assert_eq!(
+            session.set_current_player(Player::Fourth),
+            Err(StateError::NotInGame(Player::Fourth))
+        );
+    }
+
+    #[test]
+    fn test_move_ordering_prunes_more() {
+        let board = Board::new_seeded(BoardSize::Large, 7);
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+
+        ai::set_move_ordering(false);
+        ai::reset_node_count();
+        let unordered = ai::best_move(&session, Player::First, 5);
+        let unordered_nodes = ai::node_count();
+
+        ai::set_move_ordering(true);
+        ai::reset_node_count();
+        let ordered = ai::best_move(&session, Player::First, 5);
+        let ordered_nodes = ai::node_count();
+
+        // 並べ替えても選ばれる手は同じで、探索する局面は少なくなる
+        assert_eq!(ordered, unordered);
+        assert!(
+            ordered_nodes < unordered_nodes,
+            "ordered {} vs unordered {}",
+            ordered_nodes,
+            unordered_nodes
+        );
+    }
+
+    #[test]
+    fn test_is_decided() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(9));
+        board.set_piece(2, 2, Piece::Number(2));
+        board.set_piece(3, 2, Piece::Number(-1));
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        assert!(!session.is_decided());
+
+        // 先手が9を取ると、後手は残りの2を取っても -1 を負った先手に届かない
+        session.process_move((0, 2)).unwrap();
+        assert!(session.is_decided());
+
+        // 残りの正の駒で追いつける点差なら決着していない
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(3));
+        board.set_piece(2, 2, Piece::Number(5));
+        let mut session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        session.process_move((0, 2)).unwrap();
+        assert!(!session.is_round_over());
+        assert!(!session.is_decided());
+    }
+
+    #[test]
+    fn test_last_mover_bonus() {
+        let board_with_bonus = |bonus: i32| {
+            let mut board = empty_board_with_cross((0, 0));
+            board.set_piece(0, 2, Piece::Number(5));
+            board.set_piece(2, 2, Piece::Number(3));
+            board.rules.last_mover_bonus = bonus;
+            board
+        };
+
+        // ボーナスがなければ先に5を取った先手の勝ち
+        let mut session = GameSession::new_with_board(board_with_bonus(0), GameMode::TwoPlayers);
+        session.process_move((0, 2)).unwrap();
+        session.process_move((2, 2)).unwrap();
+        assert_eq!(session.get_round_winner(), Some(Player::First));
+
+        // 最後の駒を取った後手にボーナスが入り、勝者が入れ替わる
+        let mut manager = GameManager::new_with_board(board_with_bonus(4), GameMode::TwoPlayers);
+        let receiver = manager.event_channel();
+        manager.make_move((0, 2));
+        assert_eq!(manager.session.scores[&Player::First].total, 5);
+        manager.make_move((2, 2));
+        assert_eq!(manager.session.scores[&Player::Second].total, 3 + 4);
+
+        let ended = receiver
+            .try_iter()
+            .find_map(|event| match event {
+                GameEvent::RoundEnded(winner, scores, _) => Some((winner, scores)),
+                _ => None,
+            })
+            .expect("RoundEnded should be fired");
+        assert_eq!(ended.0, Some(Player::Second));
+        assert_eq!(ended.1[&Player::Second], 7);
+    }
+
+    #[test]
+    fn test_distance_to() {
+        let board = empty_board_with_cross((1, 2));
+
+        assert_eq!(board.distance_to((1, 0)), Some(2));
+        assert_eq!(board.distance_to((1, 3)), Some(1));
+        assert_eq!(board.distance_to((3, 2)), Some(2));
+        assert_eq!(board.distance_to((1, 2)), Some(0));
+
+        // 同じ行・列にないマスと盤面の外はNone
+        assert_eq!(board.distance_to((0, 0)), None);
+        assert_eq!(board.distance_to((1, 9)), None);
+    }
+
+    #[test]
+    fn test_check_invariants() {
+        let mut session = GameSession::new(BoardSize::Small, GameMode::TwoPlayers);
+        assert_eq!(session.check_invariants(), Ok(()));
+
+        // 通常の手順では毎手後も整合性が保たれる
+        while let Some(&target) = session.valid_moves(session.current_player).first() {
+            session.process_move(target).unwrap();
+            assert_eq!(session.check_invariants(), Ok(()));
+        }
+
+        // 十字の位置を壊すと検出される
+        let mut corrupted = GameSession::new(BoardSize::Small, GameMode::TwoPlayers);
+        corrupted.board.cross_position = if corrupted.board.get_piece(0, 0) == Piece::Cross {
+            (0, 1)
+        } else {
+            (0, 0)
+        };
+        assert!(corrupted.check_invariants().is_err());
+
+        // 参加していないプレイヤーの手番も検出される
+        let mut corrupted = GameSession::new(BoardSize::Small, GameMode::TwoPlayers);
+        corrupted.current_player = Player::Third;
+        assert!(corrupted.check_invariants().is_err());
+    }
+
+    #[test]
+    fn test_fill_ratio_and_game_phase() {
+        let board = Board::new_seeded(BoardSize::Small, 3);
+        assert_eq!(board.fill_ratio(), 1.0);
+        assert_eq!(board.game_phase(), GamePhase::Opening);
+
+        // 16マスのうちクロスチップと駒1つだけ
+        let mut nearly_cleared = empty_board_with_cross((0, 0));
+        nearly_cleared.set_piece(0, 3, Piece::Number(2));
+        assert_eq!(nearly_cleared.fill_ratio(), 2.0 / 16.0);
+        assert_eq!(nearly_cleared.game_phase(), GamePhase::End);
+
+        // 半分ほど埋まっていれば中盤
+        for col in 0..4 {
+            nearly_cleared.set_piece(1, col, Piece::Number(1));
+            nearly_cleared.set_piece(2, col, Piece::Number(1));
+        }
+        assert_eq!(nearly_cleared.game_phase(), GamePhase::Mid);
+    }
+
+    #[test]
+    fn test_preview_after() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        board.set_piece(2, 0, Piece::Number(3));
+        let original = board.clone();
+
+        let preview = board.preview_after(Player::First, (0, 2)).unwrap();
+        assert_eq!(preview.cross_position, (0, 2));
+        assert_eq!(preview.get_piece(0, 2), Piece::Cross);
+        assert_eq!(preview.get_piece(0, 0), Piece::Empty);
+        assert_eq!(board, original);
+
+        // 移動できない場合はエラー
+        assert_eq!(
+            board.preview_after(Player::First, (2, 0)),
+            Err(MoveError::InvalidTarget((2, 0)))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_replay_annotations_round_trip() {
+        let mut session = GameSession::new_with_board(
+            Board::new_seeded(BoardSize::Small, 11),
+            GameMode::TwoPlayers,
+        );
+        for _ in 0..3 {
+            let target = session.valid_moves(session.current_player)[0];
+            session.process_move(target).unwrap();
+        }
+        let mut replay = Replay::record(11, &session);
+
+        replay.annotate(2, "Keeps the big piece for later").unwrap();
+        assert_eq!(
+            replay.annotate(3, "too far"),
+            Err(ReplayError::IndexOutOfRange(3))
+        );
+
+        let json = serde_json::to_string(&replay).unwrap();
+        let parsed: Replay = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.annotation(2), Some("Keeps the big piece for later"));
+        assert_eq!(parsed.annotation(1), None);
+        assert_eq!(parsed, replay);
+
+        // 注釈のない古い形式も読み込める
+        let legacy = r#"{"seed":11,"size":"Small","mode":"TwoPlayers","moves":[]}"#;
+        let parsed: Replay = serde_json::from_str(legacy).unwrap();
+        assert!(parsed.annotations.is_empty());
+    }
+
+    #[test]
+    fn test_cumulative_series() {
+        let mut session = GameSession::new(BoardSize::Small, GameMode::TwoPlayers);
+        assert!(session.cumulative_series(Player::First).is_empty());
+
+        finish_scripted_round(
+            &mut session,
+            &[(Player::First, &[5]), (Player::Second, &[2])],
+        );
+        finish_scripted_round(
+            &mut session,
+            &[(Player::First, &[-3]), (Player::Second, &[4, 1])],
+        );
+        finish_scripted_round(
+            &mut session,
+            &[(Player::First, &[6, 1]), (Player::Second, &[])],
+        );
+
+        assert_eq!(session.cumulative_series(Player::First), vec![5, 2, 9]);
+        assert_eq!(session.cumulative_series(Player::Second), vec![2, 7, 7]);
+
+        // 最後の値は合計得点と一致する
+        assert_eq!(session.total_scores[&Player::First], 9);
+    }
+
+    #[test]
+    fn test_would_end_round() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(4));
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+
+        // 最後の駒を取る手はラウンドを終わらせる（セッションは変更されない）
+        assert_eq!(session.would_end_round((0, 2)), Ok(true));
+        assert_eq!(session.board.get_piece(0, 2), Piece::Number(4));
+        assert!(session.history.is_empty());
+
+        // 移動できないマスはエラー
+        assert_eq!(
+            session.would_end_round((2, 0)),
+            Err(MoveError::NotOnLine((2, 0)))
+        );
+
+        // 次のプレイヤーが動ける手はラウンドを終わらせない
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(4));
+        board.set_piece(3, 2, Piece::Number(1));
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        assert_eq!(session.would_end_round((0, 2)), Ok(false));
+    }
+
+    #[cfg(all(feature = "serde", feature = "console_ui"))]
+    #[test]
+    fn test_console_events_json_stdout_is_json_only() {
+        use std::io::Write;
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new(env!("CARGO_BIN_EXE_micattix-console"))
+            .args(["--events-json", "--seed", "5"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+        // 4x4、2人モードで1手指して終了
+        child
+            .stdin
+            .take()
+            .unwrap()
+            .write_all(b"1\n1\n1\nquit\n")
+            .unwrap();
+        let output = child.wait_with_output().unwrap();
+        assert!(output.status.success());
+
+        // 標準出力はJSON行だけで、対話用の表示は標準エラーに出る
+        let stdout = String::from_utf8(output.stdout).unwrap();
+        let lines: Vec<&str> = stdout.lines().collect();
+        assert!(lines.len() >= 3);
+        for line in &lines {
+            let value: serde_json::Value = serde_json::from_str(line)
+                .unwrap_or_else(|e| panic!("non-JSON line {:?}: {}", line, e));
+            assert!(value.get("event").is_some());
+        }
+        assert!(lines.last().unwrap().contains("GameEnded"));
+        assert!(String::from_utf8(output.stderr)
+            .unwrap()
+            .contains("Welcome to Micattix!"));
+    }
+
+    #[test]
+    fn test_apply_event_log_restores_generated_board() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        let initial = GameSession::new_with_board(board.clone(), GameMode::TwoPlayers);
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        let receiver = manager.event_channel();
+
+        manager.start_game();
+        manager.make_move((0, 2));
+        manager.start_next_round();
+
+        // 2ラウンド目の盤面は新たに生成せず、BoardGenerated の盤面を使う
+        let events: Vec<GameEvent> = receiver.try_iter().collect();
+        let rebuilt = GameManager::apply_event_log(initial, &events).unwrap();
+        assert_eq!(rebuilt.session.round, 2);
+        assert_eq!(rebuilt.session.board, manager.session.board);
+        assert_eq!(rebuilt.session.total_scores, manager.session.total_scores);
+    }
+
+    #[test]
+    fn test_apply_event_log_two_rounds() {
+        let initial = GameSession::new(BoardSize::Small, GameMode::TwoPlayers);
+        let mut manager = GameManager::new_with_board(initial.board.clone(), GameMode::TwoPlayers);
+        let receiver = manager.event_channel();
+
+        // 1ラウンド目を最後まで指し、2ラウンド目を途中まで指す
+        manager.start_game();
+        while let Some(&target) = manager.current_state().valid_moves().first() {
+            manager.make_move(target);
+        }
+        manager.start_next_round();
+        for _ in 0..3 {
+            if let Some(&target) = manager.current_state().valid_moves().first() {
+                manager.make_move(target);
+            }
+        }
+
+        let events: Vec<GameEvent> = receiver.try_iter().collect();
+        let rebuilt = GameManager::apply_event_log(initial.clone(), &events).unwrap();
+        assert_eq!(rebuilt.session.round, 2);
+        assert_eq!(rebuilt.session.board, manager.session.board);
+        assert_eq!(rebuilt.session.history, manager.session.history);
+        assert_eq!(rebuilt.session.total_scores, manager.session.total_scores);
+        assert_eq!(
+            rebuilt.session.current_player,
+            manager.session.current_player
+        );
+
+        // 適用できない手はイベントの番号とともにエラーになる
+        let mut corrupt = events.clone();
+        let index = corrupt
+            .iter()
+            .rposition(|e| matches!(e, GameEvent::MoveMade(..)))
+            .unwrap();
+        if let GameEvent::MoveMade(_, target, _) = &mut corrupt[index] {
+            *target = (9, 9);
+        }
+        assert_eq!(
+            GameManager::apply_event_log(initial.clone(), &corrupt).err(),
+            Some(EventLogError::IllegalMove(
+                index,
+                MoveError::InvalidTarget((9, 9))
+            ))
+        );
+
+        // 新しいラウンドの盤面がなければ再構築できない
+        let missing: Vec<GameEvent> = events
+            .iter()
+            .filter(|e| !matches!(e, GameEvent::BoardGenerated(_)))
+            .cloned()
+            .collect();
+        let round_two = missing
+            .iter()
+            .position(|e| matches!(e, GameEvent::RoundStarted(2)))
+            .unwrap();
+        assert_eq!(
+            GameManager::apply_event_log(initial, &missing).err(),
+            Some(EventLogError::MissingBoard(round_two))
+        );
+    }
+
+    #[test]
+    fn test_last_mover_bonus_in_predictions() {
+        let board_with_bonus = |bonus: i32| {
+            let mut board = empty_board_with_cross((0, 0));
+            board.set_piece(0, 2, Piece::Number(9));
+            board.set_piece(2, 2, Piece::Number(2));
+            board.rules.last_mover_bonus = bonus;
+            board
+        };
+
+        // ボーナスがなければ後手は追いつけない
+        let mut session = GameSession::new_with_board(board_with_bonus(0), GameMode::TwoPlayers);
+        session.process_move((0, 2)).unwrap();
+        assert!(session.is_decided());
+
+        // 最後の駒を取るボーナスで後手が逆転できるので決着していない
+        let mut session = GameSession::new_with_board(board_with_bonus(8), GameMode::TwoPlayers);
+        assert_eq!(session.score_delta(Player::First, (0, 2)), Ok(9));
+        session.process_move((0, 2)).unwrap();
+        assert_eq!(session.max_reachable_score(Player::Second), 2 + 8);
+        assert!(!session.is_decided());
+        assert_eq!(session.score_delta(Player::Second, (2, 2)), Ok(2 + 8));
+        assert_eq!(session.move_menu(), vec![(1, (2, 2), 10)]);
+        assert_eq!(ai::solve(&session), (10 - 9, Some((2, 2))));
+
+        // 特殊マスを取ってボーナスを得た場合も ScoreUpdated が通知される
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 3, Piece::Bonus(BonusKind::ExtraTurn));
+        board.rules.last_mover_bonus = 3;
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        let receiver = manager.event_channel();
+        manager.make_move((0, 3));
+        assert!(receiver
+            .try_iter()
+            .any(|event| matches!(event, GameEvent::ScoreUpdated(Player::First, 3))));
+    }
+
+    #[test]
+    fn test_set_game_mode_keeps_settings() {
+        let mut manager = GameManager::new(BoardSize::Small, GameMode::TwoPlayers);
+        manager.session.target_score = Some(30);
+        manager.session.max_rounds = Some(3);
+        manager.session.tie_break = TieBreak::MostRoundsWon;
+        manager
+            .session
+            .directions
+            .insert(Player::First, MoveDirection::Vertical);
+
+        manager.set_game_mode(GameMode::FourPlayers).unwrap();
+
+        // モードに依存しない設定は残る
+        assert_eq!(manager.session.target_score, Some(30));
+        assert_eq!(manager.session.max_rounds, Some(3));
+        assert_eq!(manager.session.tie_break, TieBreak::MostRoundsWon);
+        assert_eq!(
+            manager.session.directions[&Player::First],
+            MoveDirection::Vertical
+        );
+        assert_eq!(
+            manager.session.directions[&Player::Third],
+            MoveDirection::Horizontal
+        );
+
+        // 2人モードに戻すと参加しなくなったプレイヤーの情報は消える
+        manager.set_game_mode(GameMode::TwoPlayers).unwrap();
+        assert_eq!(manager.session.target_score, Some(30));
+        assert!(!manager.session.directions.contains_key(&Player::Third));
+        assert!(!manager.session.scores.contains_key(&Player::Third));
+    }
+
+    #[test]
+    fn test_apply_event_log_finished_game() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        let initial = GameSession::new_with_board(board.clone(), GameMode::TwoPlayers);
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+        manager.session.max_rounds = Some(1);
+        let receiver = manager.event_channel();
+
+        // 1手でラウンドが終わり、最大ラウンド数に達してゲームが終了する
+        manager.start_game();
+        manager.make_move((0, 2));
+        assert!(manager.session.is_game_over());
+
+        let events: Vec<GameEvent> = receiver.try_iter().collect();
+        assert!(matches!(events.last(), Some(GameEvent::GameEnded(..))));
+        let rebuilt = GameManager::apply_event_log(initial, &events).unwrap();
+
+        // 最後のラウンドも合計と結果に含まれる
+        assert_eq!(rebuilt.session.total_scores[&Player::First], 5);
+        assert_eq!(rebuilt.session.total_scores, manager.session.total_scores);
+        assert_eq!(rebuilt.session.total_pieces, manager.session.total_pieces);
+        assert_eq!(rebuilt.session.game_result(), manager.session.game_result());
+        assert_eq!(rebuilt.session.game_result().rounds.len(), 1);
+    }
+
+    #[test]
+    fn test_undo_round_after_game_end() {
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 2, Piece::Number(5));
+        let mut manager = GameManager::new_with_board(board, GameMode::TwoPlayers);
+
+        manager.make_move((0, 2));
+        manager.end_game();
+        assert_eq!(manager.session.total_scores[&Player::First], 5);
+
+        // 合計に記録済みのラウンドは取り消せない
+        manager.undo_round();
+        assert_eq!(manager.session.history.len(), 1);
+        assert_eq!(manager.session.board.cross_position, (0, 2));
+
+        manager.end_game();
+        assert_eq!(manager.session.total_scores[&Player::First], 5);
+        assert_eq!(manager.session.game_result().rounds.len(), 1);
+    }
+
+    #[test]
+    fn test_explain_best_move_next_player() {
+        // 特殊マスを取ると同じプレイヤーが横方向にもう一度移動する
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 1, Piece::Bonus(BonusKind::ExtraTurn));
+        board.set_piece(0, 3, Piece::Number(4));
+        board.set_piece(2, 1, Piece::Number(9));
+        board.set_piece(2, 3, Piece::Number(1));
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        let explanation = session.explain_best_move(Player::First).unwrap();
+        assert_eq!(explanation.target, (0, 3));
+        assert_eq!(explanation.denies_opponent, Some(4 - 1));
+
+        // 棄権したプレイヤーは飛ばし、次に手番が来る Third（横方向）で評価する
+        let mut board = empty_board_with_cross((0, 0));
+        board.set_piece(0, 1, Piece::Number(5));
+        board.set_piece(2, 1, Piece::Number(8));
+        board.set_piece(0, 2, Piece::Number(3));
+        board.set_piece(2, 2, Piece::Number(1));
+        let mut session = GameSession::new_with_board(board, GameMode::FourPlayers);
+        session.forfeit(Player::Second);
+        let explanation = session.explain_best_move(Player::First).unwrap();
+        assert_eq!(explanation.target, (0, 1));
+        assert_eq!(explanation.denies_opponent, Some(5 - 3));
+
+        // Movement::Magnet では通過した駒も取得値に含める
+        let mut board = empty_board_with_cross((1, 0));
+        board.set_piece(1, 1, Piece::Number(2));
+        board.set_piece(1, 2, Piece::Number(-3));
+        board.set_piece(1, 3, Piece::Number(7));
+        board.rules.movement = Movement::Magnet;
+        let session = GameSession::new_with_board(board, GameMode::TwoPlayers);
+        let explanation = session.explain_best_move(Player::First).unwrap();
+        assert_eq!(explanation.target, (1, 3));
+        assert_eq!(explanation.immediate_value, 2 - 3 + 7);
+    }
 }